@@ -0,0 +1,415 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, wasm_execute, wasm_instantiate, Addr, Binary, Deps, DepsMut, Empty, Env,
+    MessageInfo, Querier, Reply, Response, StdResult, Storage, SubMsg, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_utils::parse_reply_instantiate_data;
+use desmos_bindings::{
+    msg::DesmosMsg,
+    profiles::{models_app_links::ApplicationLinkState, querier::ProfilesQuerier},
+    query::DesmosQuery,
+    subspaces::querier::SubspacesQuerier,
+};
+use poap::msg::MintStartEndTimeResponse;
+use poap::{ExecuteMsg as PoapExecuteMsg, Extension as PoapExtension, QueryMsg as PoapQueryMsg};
+use std::ops::Deref;
+
+use crate::error::ContractError;
+use crate::msg::{ClaimCondition, ExecuteMsg, InstantiateMsg, QueryConfigResponse, QueryMsg};
+use crate::state::{ConfigState, CLAIM_CONDITIONS, CONFIG, POAP_CONTRACT_ADDRESS};
+
+// version info for migration info, the `crates.io:poap-manager` name is used by the
+// v1 manager that the `poap` contract is able to migrate from.
+const CONTRACT_NAME: &str = "crates.io:poap-manager-v2";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const INSTANTIATE_POAP_REPLY_ID: u64 = 1;
+
+// actions for executing messages
+const ACTION_INSTANTIATE: &str = "instantiate";
+const ACTION_INSTANTIATE_POAP_REPLY: &str = "instantiate_poap_reply";
+const ACTION_CLAIM: &str = "claim";
+const ACTION_MINT_TO: &str = "mint_to";
+const ACTION_UPDATE_ADMIN: &str = "update_admin";
+const ACTION_UPDATE_CLAIM_CONDITIONS: &str = "update_claim_conditions";
+const ACTION_SET_MINTABLE: &str = "set_mintable";
+
+// attributes for executing messages
+const ATTRIBUTE_ACTION: &str = "action";
+const ATTRIBUTE_SENDER: &str = "sender";
+const ATTRIBUTE_ADMIN: &str = "admin";
+const ATTRIBUTE_POAP_CONTRACT_CODE_ID: &str = "poap_contract_code_id";
+const ATTRIBUTE_POAP_CONTRACT_ADDRESS: &str = "poap_contract_address";
+const ATTRIBUTE_NEW_ADMIN: &str = "new_admin";
+const ATTRIBUTE_RECIPIENT: &str = "recipient";
+const ATTRIBUTE_CLAIM_CONDITIONS: &str = "claim_conditions";
+const ATTRIBUTE_MINTABLE: &str = "mintable";
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut<DesmosQuery>,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response<DesmosMsg>, ContractError> {
+    msg.validate()?;
+    // Save the config
+    let admin_addr = deps.api.addr_validate(&msg.admin)?;
+    CONFIG.save(
+        deps.storage,
+        &ConfigState {
+            admin: admin_addr,
+            poap_contract_code_id: msg.poap_contract_code_id,
+            is_mintable: msg.poap_instantiate_msg.is_mintable,
+        },
+    )?;
+    CLAIM_CONDITIONS.save(deps.storage, &msg.claim_conditions)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    // Submessage to instantiate the POAP contract, this contract becomes its minter
+    // and the users can't mint by themselves, so that the POAPs can be minted only
+    // through the claim procedure. The requested mintable flag is kept by this
+    // contract to enable or disable the claims.
+    let mut poap_instantiate_msg = msg.poap_instantiate_msg;
+    poap_instantiate_msg.minter = Some(env.contract.address.to_string());
+    poap_instantiate_msg.is_mintable = false;
+    poap_instantiate_msg.admin = poap_instantiate_msg.admin.or(Some(msg.admin.clone()));
+    let poap_submessage = SubMsg::reply_on_success(
+        wasm_instantiate(
+            msg.poap_contract_code_id,
+            &poap_instantiate_msg,
+            info.funds,
+            "poap_manager_poap".to_string(),
+        )?,
+        INSTANTIATE_POAP_REPLY_ID,
+    );
+    Ok(Response::new()
+        .add_attribute(ATTRIBUTE_ACTION, ACTION_INSTANTIATE)
+        .add_attribute(ATTRIBUTE_SENDER, &info.sender)
+        .add_attribute(ATTRIBUTE_ADMIN, msg.admin)
+        .add_attribute(
+            ATTRIBUTE_POAP_CONTRACT_CODE_ID,
+            msg.poap_contract_code_id.to_string(),
+        )
+        .add_submessage(poap_submessage))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut<DesmosQuery>,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response<DesmosMsg>, ContractError> {
+    msg.validate()?;
+    match msg {
+        ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::MintTo { user } => execute_mint_to(deps, info, user),
+        ExecuteMsg::UpdateAdmin { new_admin } => execute_update_admin(deps, info, new_admin),
+        ExecuteMsg::UpdateClaimConditions { claim_conditions } => {
+            execute_update_claim_conditions(deps, info, claim_conditions)
+        }
+        ExecuteMsg::SetMintable { mintable } => execute_set_mintable(deps, info, mintable),
+    }
+}
+
+fn execute_claim(
+    deps: DepsMut<DesmosQuery>,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response<DesmosMsg>, ContractError> {
+    check_claim_period(deps.as_ref(), &env)?;
+    let claim_conditions = CLAIM_CONDITIONS.load(deps.storage)?;
+    check_claim_conditions(deps.querier.deref(), &info.sender, &claim_conditions)?;
+    let mint_msg = poap_mint_to_msg(deps.storage, &info.sender)?;
+    Ok(Response::new()
+        .add_attribute(ATTRIBUTE_ACTION, ACTION_CLAIM)
+        .add_attribute(ATTRIBUTE_SENDER, &info.sender)
+        .add_message(mint_msg))
+}
+
+fn execute_mint_to(
+    deps: DepsMut<DesmosQuery>,
+    info: MessageInfo,
+    user: String,
+) -> Result<Response<DesmosMsg>, ContractError> {
+    check_admin(deps.storage, &info)?;
+    let user_addr = deps.api.addr_validate(&user)?;
+    // The admin can bypass the claim conditions, but the user still needs a profile.
+    check_claim_conditions(
+        deps.querier.deref(),
+        &user_addr,
+        &[ClaimCondition::HasProfile {}],
+    )?;
+    let mint_msg = poap_mint_to_msg(deps.storage, &user_addr)?;
+    Ok(Response::new()
+        .add_attribute(ATTRIBUTE_ACTION, ACTION_MINT_TO)
+        .add_attribute(ATTRIBUTE_SENDER, &info.sender)
+        .add_attribute(ATTRIBUTE_RECIPIENT, &user_addr)
+        .add_message(mint_msg))
+}
+
+/// Builds the message that mints a POAP to the given user through the POAP contract.
+fn poap_mint_to_msg(storage: &dyn Storage, user: &Addr) -> StdResult<WasmMsg> {
+    let poap_contract_address = POAP_CONTRACT_ADDRESS.load(storage)?;
+    wasm_execute(
+        poap_contract_address,
+        &PoapExecuteMsg::<PoapExtension, Empty>::MintTo {
+            event_id: None,
+            users: vec![user.to_string()],
+            extension: None,
+            skip_invalid: None,
+        },
+        vec![],
+    )
+}
+
+/// Checks that the POAP can be claimed at the current block.
+/// The POAP contract doesn't apply its mint period to the `MintTo` messages sent
+/// from this contract, so the period is checked here before sending them.
+fn check_claim_period(deps: Deps<DesmosQuery>, env: &Env) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.is_mintable {
+        return Err(ContractError::ClaimDisabled {});
+    }
+    let poap_contract_address = POAP_CONTRACT_ADDRESS.load(deps.storage)?;
+    let mint_time: MintStartEndTimeResponse = deps.querier.query_wasm_smart(
+        poap_contract_address,
+        &PoapQueryMsg::<Empty>::MintStartEndTime {},
+    )?;
+    if let Some(start_time) = mint_time.start_time {
+        if !start_time.is_triggered(&env.block) {
+            return Err(ContractError::ClaimNotStarted {});
+        }
+    }
+    if let Some(end_time) = mint_time.end_time {
+        if end_time.is_expired(&env.block) {
+            return Err(ContractError::ClaimEnded {});
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the user satisfies all the claim conditions.
+fn check_claim_conditions(
+    querier: &dyn Querier,
+    user: &Addr,
+    claim_conditions: &[ClaimCondition],
+) -> Result<(), ContractError> {
+    for condition in claim_conditions {
+        match condition {
+            ClaimCondition::HasProfile {} => {
+                ProfilesQuerier::new(querier)
+                    .query_profile(user.clone())
+                    .map_err(|_| ContractError::ProfileRequired {
+                        user: user.to_string(),
+                    })?;
+            }
+            ClaimCondition::DtagMatches { pattern } => {
+                let profile = ProfilesQuerier::new(querier)
+                    .query_profile(user.clone())
+                    .map_err(|_| ContractError::ProfileRequired {
+                        user: user.to_string(),
+                    })?
+                    .profile;
+                if !matches_pattern(&profile.dtag, pattern) {
+                    return Err(ContractError::DtagNotMatching {
+                        user: user.to_string(),
+                        pattern: pattern.clone(),
+                    });
+                }
+            }
+            ClaimCondition::HasAppLink { application } => {
+                let mut has_app_link = false;
+                for app_link in ProfilesQuerier::new(querier).iterate_application_links(
+                    Some(user.clone()),
+                    Some(application.clone()),
+                    None,
+                    10,
+                ) {
+                    if app_link?.state == ApplicationLinkState::VerificationSuccess {
+                        has_app_link = true;
+                        break;
+                    }
+                }
+                if !has_app_link {
+                    return Err(ContractError::AppLinkRequired {
+                        user: user.to_string(),
+                        application: application.clone(),
+                    });
+                }
+            }
+            ClaimCondition::HasSubspacePermission {
+                subspace_id,
+                section_id,
+                permission,
+            } => {
+                let permissions = SubspacesQuerier::new(querier)
+                    .query_user_permissions(subspace_id.u64(), *section_id, user.clone())?
+                    .permissions;
+                if !permissions.iter().any(|granted| granted == permission) {
+                    return Err(ContractError::SubspacePermissionRequired {
+                        user: user.to_string(),
+                        subspace_id: subspace_id.u64(),
+                        permission: permission.clone(),
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks if the value matches the pattern, where `*` matches any sequence
+/// of characters and `?` matches a single character.
+pub fn matches_pattern(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let (mut value_index, mut pattern_index) = (0, 0);
+    // Position of the last `*` inside the pattern and of the value character it is matching.
+    let mut backtrack: Option<(usize, usize)> = None;
+    while value_index < value.len() {
+        match pattern.get(pattern_index) {
+            Some('*') => {
+                backtrack = Some((pattern_index, value_index));
+                pattern_index += 1;
+            }
+            Some(c) if *c == '?' || *c == value[value_index] => {
+                value_index += 1;
+                pattern_index += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` match one more character and try again.
+                Some((star_index, matched_index)) => {
+                    backtrack = Some((star_index, matched_index + 1));
+                    pattern_index = star_index + 1;
+                    value_index = matched_index + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[pattern_index..].iter().all(|c| *c == '*')
+}
+
+fn execute_update_admin(
+    deps: DepsMut<DesmosQuery>,
+    info: MessageInfo,
+    new_admin: String,
+) -> Result<Response<DesmosMsg>, ContractError> {
+    check_admin(deps.storage, &info)?;
+    let new_admin_addr = deps.api.addr_validate(&new_admin)?;
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.admin = new_admin_addr;
+        Ok(config)
+    })?;
+    Ok(Response::new()
+        .add_attribute(ATTRIBUTE_ACTION, ACTION_UPDATE_ADMIN)
+        .add_attribute(ATTRIBUTE_SENDER, &info.sender)
+        .add_attribute(ATTRIBUTE_ADMIN, &info.sender)
+        .add_attribute(ATTRIBUTE_NEW_ADMIN, new_admin))
+}
+
+fn execute_update_claim_conditions(
+    deps: DepsMut<DesmosQuery>,
+    info: MessageInfo,
+    claim_conditions: Vec<ClaimCondition>,
+) -> Result<Response<DesmosMsg>, ContractError> {
+    check_admin(deps.storage, &info)?;
+    CLAIM_CONDITIONS.save(deps.storage, &claim_conditions)?;
+    Ok(Response::new()
+        .add_attribute(ATTRIBUTE_ACTION, ACTION_UPDATE_CLAIM_CONDITIONS)
+        .add_attribute(ATTRIBUTE_SENDER, &info.sender)
+        .add_attribute(
+            ATTRIBUTE_CLAIM_CONDITIONS,
+            claim_conditions.len().to_string(),
+        ))
+}
+
+fn execute_set_mintable(
+    deps: DepsMut<DesmosQuery>,
+    info: MessageInfo,
+    mintable: bool,
+) -> Result<Response<DesmosMsg>, ContractError> {
+    check_admin(deps.storage, &info)?;
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.is_mintable = mintable;
+        Ok(config)
+    })?;
+    Ok(Response::new()
+        .add_attribute(ATTRIBUTE_ACTION, ACTION_SET_MINTABLE)
+        .add_attribute(ATTRIBUTE_SENDER, &info.sender)
+        .add_attribute(ATTRIBUTE_MINTABLE, mintable.to_string()))
+}
+
+fn check_admin(storage: &dyn Storage, info: &MessageInfo) -> Result<(), ContractError> {
+    let config = CONFIG.load(storage)?;
+    if config.admin != info.sender {
+        return Err(ContractError::NotAdmin {
+            caller: info.sender.clone(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps<DesmosQuery>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+    }
+}
+
+fn query_config(deps: Deps<DesmosQuery>) -> StdResult<QueryConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let poap_contract_address = POAP_CONTRACT_ADDRESS.load(deps.storage)?;
+    let claim_conditions = CLAIM_CONDITIONS.load(deps.storage)?;
+    Ok(QueryConfigResponse {
+        admin: config.admin,
+        poap_contract_code_id: config.poap_contract_code_id,
+        poap_contract_address,
+        claim_conditions,
+        is_mintable: config.is_mintable,
+    })
+}
+
+// Reply callback triggered from the POAP contract instantiation
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
+    deps: DepsMut<DesmosQuery>,
+    _env: Env,
+    msg: Reply,
+) -> Result<Response<DesmosMsg>, ContractError> {
+    if msg.id != INSTANTIATE_POAP_REPLY_ID {
+        return Err(ContractError::InvalidReplyID {});
+    }
+    let reply = parse_reply_instantiate_data(msg);
+    match reply {
+        Ok(res) => {
+            let poap_contract_address = Addr::unchecked(res.contract_address);
+            POAP_CONTRACT_ADDRESS.save(deps.storage, &poap_contract_address)?;
+            Ok(Response::default()
+                .add_attribute(ATTRIBUTE_ACTION, ACTION_INSTANTIATE_POAP_REPLY)
+                .add_attribute(ATTRIBUTE_POAP_CONTRACT_ADDRESS, poap_contract_address))
+        }
+        Err(_) => Err(ContractError::InstantiatePoapError {}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_pattern_properly() {
+        assert!(matches_pattern("goldrake", "goldrake"));
+        assert!(matches_pattern("goldrake", "gold*"));
+        assert!(matches_pattern("goldrake", "*rake"));
+        assert!(matches_pattern("goldrake", "g*d*e"));
+        assert!(matches_pattern("goldrake", "gol?rake"));
+        assert!(matches_pattern("goldrake", "*"));
+        assert!(!matches_pattern("goldrake", "gold"));
+        assert!(!matches_pattern("goldrake", "silver*"));
+        assert!(!matches_pattern("goldrake", "gol?"));
+        assert!(!matches_pattern("", "?"));
+    }
+}
@@ -0,0 +1,51 @@
+use cosmwasm_std::{Addr, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Caller is not admin: {caller}")]
+    NotAdmin { caller: Addr },
+
+    #[error("Invalid POAP contract code id")]
+    InvalidPoapContractCodeId {},
+
+    #[error("Invalid reply ID")]
+    InvalidReplyID {},
+
+    #[error("Instantiate POAP contract error")]
+    InstantiatePoapError {},
+
+    #[error("The POAP can't be claimed")]
+    ClaimDisabled {},
+
+    #[error("Can't claim: minting period not started yet")]
+    ClaimNotStarted {},
+
+    #[error("Can't claim: minting period already ended")]
+    ClaimEnded {},
+
+    #[error("Invalid DTag pattern: {pattern}")]
+    InvalidDtagPattern { pattern: String },
+
+    #[error("Invalid subspace ID")]
+    InvalidSubspaceId {},
+
+    #[error("{user} must have a Desmos profile to claim the POAP")]
+    ProfileRequired { user: String },
+
+    #[error("DTag of {user} doesn't match the pattern {pattern}")]
+    DtagNotMatching { user: String, pattern: String },
+
+    #[error("{user} must have a verified {application} link to claim the POAP")]
+    AppLinkRequired { user: String, application: String },
+
+    #[error("{user} must have the {permission} permission inside the subspace {subspace_id}")]
+    SubspacePermissionRequired {
+        user: String,
+        subspace_id: u64,
+        permission: String,
+    },
+}
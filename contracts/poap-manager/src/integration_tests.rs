@@ -0,0 +1,531 @@
+#[cfg(test)]
+mod tests {
+    use crate::msg::{ClaimCondition, ExecuteMsg, InstantiateMsg, QueryConfigResponse, QueryMsg};
+    use crate::test_utils::*;
+    use crate::ContractError;
+    use cosmwasm_std::{wasm_execute, Addr, Empty};
+    use cw721::TokensResponse;
+    use cw_multi_test::{AppResponse, Contract, ContractWrapper, Executor};
+    use cw_utils::{Expiration, Scheduled};
+    use desmos_bindings::{
+        mocks::mock_apps::{custom_desmos_app, DesmosApp, DesmosModule},
+        msg::DesmosMsg,
+        query::DesmosQuery,
+    };
+    use poap::msg::ConfigResponse as PoapConfigResponse;
+    use poap::QueryMsg as PoapQueryMsg;
+
+    fn mock_desmos_app() -> DesmosApp<DesmosKeeper> {
+        custom_desmos_app(DesmosKeeper {}, |_, _, _| {})
+    }
+    fn contract_poap_manager() -> Box<dyn Contract<DesmosMsg, DesmosQuery>> {
+        let contract = ContractWrapper::new(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        )
+        .with_reply(crate::contract::reply);
+        Box::new(contract)
+    }
+    fn store_contracts<M: DesmosModule>(app: &mut DesmosApp<M>) -> (u64, u64) {
+        let poap_code_id = app.store_code(poap_contract());
+        let poap_manager_code_id = app.store_code(contract_poap_manager());
+        (poap_code_id, poap_manager_code_id)
+    }
+    fn get_valid_init_msg(poap_code_id: u64) -> InstantiateMsg {
+        InstantiateMsg {
+            admin: ADMIN.into(),
+            poap_contract_code_id: poap_code_id,
+            poap_instantiate_msg: get_poap_instantiate_msg(),
+            claim_conditions: vec![
+                ClaimCondition::HasProfile {},
+                ClaimCondition::DtagMatches {
+                    pattern: "gold*".into(),
+                },
+                ClaimCondition::HasAppLink {
+                    application: APPLICATION.into(),
+                },
+                ClaimCondition::HasSubspacePermission {
+                    subspace_id: SUBSPACE_ID,
+                    section_id: SECTION_ID,
+                    permission: PERMISSION.into(),
+                },
+            ],
+        }
+    }
+    fn proper_instantiate<M: DesmosModule>(app: &mut DesmosApp<M>) -> Addr {
+        let (poap_code_id, poap_manager_code_id) = store_contracts(app);
+        instantiate_with_msg(app, poap_manager_code_id, &get_valid_init_msg(poap_code_id))
+    }
+    fn instantiate_with_msg<M: DesmosModule>(
+        app: &mut DesmosApp<M>,
+        poap_manager_code_id: u64,
+        msg: &InstantiateMsg,
+    ) -> Addr {
+        app.instantiate_contract(
+            poap_manager_code_id,
+            Addr::unchecked(ADMIN),
+            msg,
+            &[],
+            "poap_manager_contract",
+            None,
+        )
+        .unwrap()
+    }
+    fn query_config<M: DesmosModule>(app: &DesmosApp<M>, addr: &Addr) -> QueryConfigResponse {
+        app.wrap()
+            .query_wasm_smart(addr, &QueryMsg::Config {})
+            .unwrap()
+    }
+    fn query_poaps<M: DesmosModule>(
+        app: &DesmosApp<M>,
+        poap_address: &Addr,
+        owner: &str,
+    ) -> Vec<String> {
+        let response: TokensResponse = app
+            .wrap()
+            .query_wasm_smart(
+                poap_address,
+                &PoapQueryMsg::<Empty>::Tokens {
+                    owner: owner.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        response.tokens
+    }
+    fn execute_manager<M: DesmosModule>(
+        app: &mut DesmosApp<M>,
+        sender: &str,
+        addr: &Addr,
+        msg: &ExecuteMsg,
+    ) -> anyhow::Result<AppResponse> {
+        app.execute(
+            Addr::unchecked(sender),
+            wasm_execute(addr, msg, vec![]).unwrap().into(),
+        )
+    }
+    mod instantiate {
+        use super::*;
+        #[test]
+        fn instantiate_with_invalid_poap_contract_code_id_error() {
+            let mut app = mock_desmos_app();
+            let (poap_code_id, poap_manager_code_id) = store_contracts(&mut app);
+            let mut init_msg = get_valid_init_msg(poap_code_id);
+            init_msg.poap_contract_code_id = 0;
+            let init_result = app.instantiate_contract(
+                poap_manager_code_id,
+                Addr::unchecked(ADMIN),
+                &init_msg,
+                &[],
+                "poap_manager_contract",
+                None,
+            );
+            assert!(init_result.is_err());
+        }
+        #[test]
+        fn instantiate_with_invalid_poap_instantiate_msg_error() {
+            let mut app = mock_desmos_app();
+            let (poap_code_id, poap_manager_code_id) = store_contracts(&mut app);
+            let mut init_msg = get_valid_init_msg(poap_code_id);
+            init_msg.poap_instantiate_msg.admin = Some("".into());
+            let init_result = app.instantiate_contract(
+                poap_manager_code_id,
+                Addr::unchecked(ADMIN),
+                &init_msg,
+                &[],
+                "poap_manager_contract",
+                None,
+            );
+            assert!(init_result.is_err());
+        }
+        #[test]
+        fn instantiate_properly() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            let config = query_config(&app, &addr);
+            assert_eq!(config.admin, ADMIN);
+            assert_eq!(
+                config.claim_conditions,
+                get_valid_init_msg(config.poap_contract_code_id).claim_conditions
+            );
+            // The manager is the minter of the POAP contract, which is controlled by the manager admin
+            let poap_config: PoapConfigResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &config.poap_contract_address,
+                    &PoapQueryMsg::<Empty>::Config {},
+                )
+                .unwrap();
+            assert_eq!(poap_config.minter, Some(addr.to_string()));
+            assert_eq!(poap_config.admin, Some(ADMIN.to_string()));
+            // The users can't mint by themselves, the mintable flag enables the claims instead
+            assert!(!poap_config.is_mintable);
+            assert!(config.is_mintable);
+        }
+    }
+    mod claim {
+        use super::*;
+        #[test]
+        fn claim_properly() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            execute_manager(&mut app, USER, &addr, &ExecuteMsg::Claim {}).unwrap();
+            let config = query_config(&app, &addr);
+            assert_eq!(
+                vec!["1".to_string()],
+                query_poaps(&app, &config.poap_contract_address, USER)
+            );
+        }
+        #[test]
+        fn claim_twice_error() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            execute_manager(&mut app, USER, &addr, &ExecuteMsg::Claim {}).unwrap();
+            // The POAP contract allows a single POAP for each user by default
+            assert!(execute_manager(&mut app, USER, &addr, &ExecuteMsg::Claim {}).is_err());
+        }
+        #[test]
+        fn claim_without_profile_error() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            let error =
+                execute_manager(&mut app, USER_WITHOUT_PROFILE, &addr, &ExecuteMsg::Claim {})
+                    .unwrap_err();
+            assert_eq!(
+                ContractError::ProfileRequired {
+                    user: USER_WITHOUT_PROFILE.into()
+                },
+                error.downcast().unwrap()
+            );
+        }
+        #[test]
+        fn claim_with_not_matching_dtag_error() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            let claim_conditions = vec![ClaimCondition::DtagMatches {
+                pattern: "silver*".into(),
+            }];
+            execute_manager(
+                &mut app,
+                ADMIN,
+                &addr,
+                &ExecuteMsg::UpdateClaimConditions { claim_conditions },
+            )
+            .unwrap();
+            let error = execute_manager(&mut app, USER, &addr, &ExecuteMsg::Claim {}).unwrap_err();
+            assert_eq!(
+                ContractError::DtagNotMatching {
+                    user: USER.into(),
+                    pattern: "silver*".into(),
+                },
+                error.downcast().unwrap()
+            );
+        }
+        #[test]
+        fn claim_without_app_link_error() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            let claim_conditions = vec![ClaimCondition::HasAppLink {
+                application: "github".into(),
+            }];
+            execute_manager(
+                &mut app,
+                ADMIN,
+                &addr,
+                &ExecuteMsg::UpdateClaimConditions { claim_conditions },
+            )
+            .unwrap();
+            let error =
+                execute_manager(&mut app, USER_WITHOUT_PROFILE, &addr, &ExecuteMsg::Claim {})
+                    .unwrap_err();
+            assert_eq!(
+                ContractError::AppLinkRequired {
+                    user: USER_WITHOUT_PROFILE.into(),
+                    application: "github".into(),
+                },
+                error.downcast().unwrap()
+            );
+        }
+        #[test]
+        fn claim_without_subspace_permission_error() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            let claim_conditions = vec![ClaimCondition::HasSubspacePermission {
+                subspace_id: SUBSPACE_ID,
+                section_id: SECTION_ID,
+                permission: "MANAGE_SECTIONS".into(),
+            }];
+            execute_manager(
+                &mut app,
+                ADMIN,
+                &addr,
+                &ExecuteMsg::UpdateClaimConditions { claim_conditions },
+            )
+            .unwrap();
+            let error = execute_manager(&mut app, USER, &addr, &ExecuteMsg::Claim {}).unwrap_err();
+            assert_eq!(
+                ContractError::SubspacePermissionRequired {
+                    user: USER.into(),
+                    subspace_id: SUBSPACE_ID.u64(),
+                    permission: "MANAGE_SECTIONS".into(),
+                },
+                error.downcast().unwrap()
+            );
+        }
+        #[test]
+        fn claim_when_disabled_error() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            execute_manager(
+                &mut app,
+                ADMIN,
+                &addr,
+                &ExecuteMsg::SetMintable { mintable: false },
+            )
+            .unwrap();
+            let error = execute_manager(&mut app, USER, &addr, &ExecuteMsg::Claim {}).unwrap_err();
+            assert_eq!(ContractError::ClaimDisabled {}, error.downcast().unwrap());
+        }
+        #[test]
+        fn claim_outside_mint_period_error() {
+            let mut app = mock_desmos_app();
+            let (poap_code_id, poap_manager_code_id) = store_contracts(&mut app);
+            let height = app.block_info().height;
+            let mut init_msg = get_valid_init_msg(poap_code_id);
+            init_msg.poap_instantiate_msg.mint_start_time = Some(Scheduled::AtHeight(height + 10));
+            init_msg.poap_instantiate_msg.mint_end_time = Some(Expiration::AtHeight(height + 20));
+            let addr = instantiate_with_msg(&mut app, poap_manager_code_id, &init_msg);
+            let error = execute_manager(&mut app, USER, &addr, &ExecuteMsg::Claim {}).unwrap_err();
+            assert_eq!(ContractError::ClaimNotStarted {}, error.downcast().unwrap());
+            app.update_block(|block| block.height += 20);
+            let error = execute_manager(&mut app, USER, &addr, &ExecuteMsg::Claim {}).unwrap_err();
+            assert_eq!(ContractError::ClaimEnded {}, error.downcast().unwrap());
+        }
+        #[test]
+        fn claim_inside_mint_period_properly() {
+            let mut app = mock_desmos_app();
+            let (poap_code_id, poap_manager_code_id) = store_contracts(&mut app);
+            let height = app.block_info().height;
+            let mut init_msg = get_valid_init_msg(poap_code_id);
+            init_msg.poap_instantiate_msg.mint_start_time = Some(Scheduled::AtHeight(height + 10));
+            init_msg.poap_instantiate_msg.mint_end_time = Some(Expiration::AtHeight(height + 20));
+            let addr = instantiate_with_msg(&mut app, poap_manager_code_id, &init_msg);
+            app.update_block(|block| block.height += 10);
+            execute_manager(&mut app, USER, &addr, &ExecuteMsg::Claim {}).unwrap();
+            let config = query_config(&app, &addr);
+            assert_eq!(
+                vec!["1".to_string()],
+                query_poaps(&app, &config.poap_contract_address, USER)
+            );
+        }
+        #[test]
+        fn mint_directly_from_poap_contract_error() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            let config = query_config(&app, &addr);
+            // The POAP contract isn't mintable, so the users can't skip the claim conditions
+            // by minting by themselves.
+            let result = app.execute(
+                Addr::unchecked(USER_WITHOUT_PROFILE),
+                wasm_execute(
+                    &config.poap_contract_address,
+                    &poap::ExecuteMsg::<poap::Extension, Empty>::Mint {
+                        event_id: None,
+                        extension: None,
+                        referrer: None,
+                    },
+                    vec![],
+                )
+                .unwrap()
+                .into(),
+            );
+            assert!(result.is_err());
+            assert!(
+                query_poaps(&app, &config.poap_contract_address, USER_WITHOUT_PROFILE).is_empty()
+            );
+        }
+    }
+    mod mint_to {
+        use super::*;
+        #[test]
+        fn mint_to_without_permission_error() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            let error = execute_manager(
+                &mut app,
+                USER,
+                &addr,
+                &ExecuteMsg::MintTo { user: USER.into() },
+            )
+            .unwrap_err();
+            assert_eq!(
+                ContractError::NotAdmin {
+                    caller: Addr::unchecked(USER)
+                },
+                error.downcast().unwrap()
+            );
+        }
+        #[test]
+        fn mint_to_user_without_profile_error() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            let error = execute_manager(
+                &mut app,
+                ADMIN,
+                &addr,
+                &ExecuteMsg::MintTo {
+                    user: USER_WITHOUT_PROFILE.into(),
+                },
+            )
+            .unwrap_err();
+            assert_eq!(
+                ContractError::ProfileRequired {
+                    user: USER_WITHOUT_PROFILE.into()
+                },
+                error.downcast().unwrap()
+            );
+        }
+        #[test]
+        fn mint_to_properly() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            // Only the profile is required, the other claim conditions are bypassed
+            let claim_conditions = vec![ClaimCondition::DtagMatches {
+                pattern: "silver*".into(),
+            }];
+            execute_manager(
+                &mut app,
+                ADMIN,
+                &addr,
+                &ExecuteMsg::UpdateClaimConditions { claim_conditions },
+            )
+            .unwrap();
+            execute_manager(
+                &mut app,
+                ADMIN,
+                &addr,
+                &ExecuteMsg::MintTo { user: USER.into() },
+            )
+            .unwrap();
+            let config = query_config(&app, &addr);
+            assert_eq!(
+                vec!["1".to_string()],
+                query_poaps(&app, &config.poap_contract_address, USER)
+            );
+        }
+    }
+    mod update_admin {
+        use super::*;
+        #[test]
+        fn update_admin_without_permission_error() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            let error = execute_manager(
+                &mut app,
+                USER,
+                &addr,
+                &ExecuteMsg::UpdateAdmin {
+                    new_admin: USER.into(),
+                },
+            )
+            .unwrap_err();
+            assert_eq!(
+                ContractError::NotAdmin {
+                    caller: Addr::unchecked(USER)
+                },
+                error.downcast().unwrap()
+            );
+        }
+        #[test]
+        fn update_admin_properly() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            execute_manager(
+                &mut app,
+                ADMIN,
+                &addr,
+                &ExecuteMsg::UpdateAdmin {
+                    new_admin: USER.into(),
+                },
+            )
+            .unwrap();
+            assert_eq!(query_config(&app, &addr).admin, USER);
+        }
+    }
+    mod set_mintable {
+        use super::*;
+        #[test]
+        fn set_mintable_without_permission_error() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            let error = execute_manager(
+                &mut app,
+                USER,
+                &addr,
+                &ExecuteMsg::SetMintable { mintable: false },
+            )
+            .unwrap_err();
+            assert_eq!(
+                ContractError::NotAdmin {
+                    caller: Addr::unchecked(USER)
+                },
+                error.downcast().unwrap()
+            );
+        }
+        #[test]
+        fn set_mintable_properly() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            execute_manager(
+                &mut app,
+                ADMIN,
+                &addr,
+                &ExecuteMsg::SetMintable { mintable: false },
+            )
+            .unwrap();
+            assert!(!query_config(&app, &addr).is_mintable);
+        }
+    }
+    mod update_claim_conditions {
+        use super::*;
+        #[test]
+        fn update_claim_conditions_without_permission_error() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            let error = execute_manager(
+                &mut app,
+                USER,
+                &addr,
+                &ExecuteMsg::UpdateClaimConditions {
+                    claim_conditions: vec![],
+                },
+            )
+            .unwrap_err();
+            assert_eq!(
+                ContractError::NotAdmin {
+                    caller: Addr::unchecked(USER)
+                },
+                error.downcast().unwrap()
+            );
+        }
+        #[test]
+        fn update_claim_conditions_properly() {
+            let mut app = mock_desmos_app();
+            let addr = proper_instantiate(&mut app);
+            execute_manager(
+                &mut app,
+                ADMIN,
+                &addr,
+                &ExecuteMsg::UpdateClaimConditions {
+                    claim_conditions: vec![],
+                },
+            )
+            .unwrap();
+            assert!(query_config(&app, &addr).claim_conditions.is_empty());
+            // Without conditions every user can claim the POAP
+            execute_manager(&mut app, USER_WITHOUT_PROFILE, &addr, &ExecuteMsg::Claim {}).unwrap();
+        }
+    }
+}
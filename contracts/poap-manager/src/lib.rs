@@ -0,0 +1,10 @@
+pub mod contract;
+mod error;
+#[cfg(test)]
+pub mod integration_tests;
+pub mod msg;
+pub mod state;
+#[cfg(test)]
+pub mod test_utils;
+
+pub use crate::error::ContractError;
@@ -0,0 +1,201 @@
+use crate::ContractError;
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Uint64};
+use poap::InstantiateMsg as PoapInstantiateMsg;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Address of who will have the right to administer the contract.
+    pub admin: String,
+    /// Id of the POAP contract to initialize together with this contract.
+    pub poap_contract_code_id: u64,
+    /// Initialization message that will be sent to the POAP contract.
+    /// The minter is replaced with the address of this contract and, if not set,
+    /// the admin of the POAP contract will be the admin of this contract.
+    /// The POAP contract is instantiated as not mintable so that the users can
+    /// only claim the POAP, `is_mintable` tells if the claims are enabled instead.
+    pub poap_instantiate_msg: PoapInstantiateMsg,
+    /// Conditions that a user must satisfy to claim a POAP.
+    pub claim_conditions: Vec<ClaimCondition>,
+}
+
+impl InstantiateMsg {
+    /// Checks that the data inside the message are coherent.
+    /// NOTE: This function don't checks if the address are valid.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.poap_contract_code_id == 0 {
+            return Err(ContractError::InvalidPoapContractCodeId {});
+        }
+        self.claim_conditions
+            .iter()
+            .try_for_each(ClaimCondition::validate)
+    }
+}
+
+/// Condition that a user must satisfy to claim a POAP.
+/// The conditions are checked through the Desmos custom queries.
+#[cw_serde]
+pub enum ClaimCondition {
+    /// The user must have a Desmos profile.
+    HasProfile {},
+    /// The user DTag must match the given pattern, where `*` matches any
+    /// sequence of characters and `?` matches a single character.
+    DtagMatches { pattern: String },
+    /// The user must have a verified link to the given application.
+    HasAppLink { application: String },
+    /// The user must have the given permission inside a section of a subspace.
+    HasSubspacePermission {
+        subspace_id: Uint64,
+        section_id: u32,
+        permission: String,
+    },
+}
+
+impl ClaimCondition {
+    /// Checks that the data inside the condition are coherent.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        match self {
+            ClaimCondition::DtagMatches { pattern } => {
+                if pattern.trim().is_empty() {
+                    return Err(ContractError::InvalidDtagPattern {
+                        pattern: pattern.clone(),
+                    });
+                }
+                Ok(())
+            }
+            ClaimCondition::HasSubspacePermission { subspace_id, .. } => {
+                if subspace_id.is_zero() {
+                    return Err(ContractError::InvalidSubspaceId {});
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Message allowing the user to claim a POAP if they satisfy all the claim conditions.
+    Claim {},
+    /// Message allowing the contract's admin to mint a POAP to a user that has a Desmos profile,
+    /// bypassing the other claim conditions.
+    MintTo { user: String },
+    /// Message allowing the contract's admin to transfer the admin rights to another user.
+    UpdateAdmin { new_admin: String },
+    /// Message allowing the contract's admin to replace the claim conditions.
+    UpdateClaimConditions {
+        claim_conditions: Vec<ClaimCondition>,
+    },
+    /// Message allowing the contract's admin to enable or disable the claims.
+    SetMintable { mintable: bool },
+}
+
+impl ExecuteMsg {
+    /// Checks that the data inside the message are coherent.
+    /// NOTE: This function don't checks if the address are valid.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        match self {
+            ExecuteMsg::UpdateClaimConditions { claim_conditions } => claim_conditions
+                .iter()
+                .try_for_each(ClaimCondition::validate),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns the configuration info as a [`QueryConfigResponse`].
+    #[returns(QueryConfigResponse)]
+    Config {},
+}
+
+/// Response to [`QueryMsg::Config`].
+#[cw_serde]
+pub struct QueryConfigResponse {
+    /// Address of the contract administrator.
+    pub admin: Addr,
+    /// Id of the POAP contract that this contract has initialized.
+    pub poap_contract_code_id: u64,
+    /// Address of the POAP contract that this contract is using to mint.
+    pub poap_contract_address: Addr,
+    /// Conditions that a user must satisfy to claim a POAP.
+    pub claim_conditions: Vec<ClaimCondition>,
+    /// Whether the users can claim the POAP.
+    pub is_mintable: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::get_poap_instantiate_msg;
+    mod instantiate {
+        use super::*;
+        #[test]
+        fn instantiate_msg_with_invalid_poap_contract_code_id_error() {
+            let msg = InstantiateMsg {
+                admin: "admin".into(),
+                poap_contract_code_id: 0,
+                poap_instantiate_msg: get_poap_instantiate_msg(),
+                claim_conditions: vec![],
+            };
+            assert_eq!(
+                ContractError::InvalidPoapContractCodeId {},
+                msg.validate().unwrap_err()
+            )
+        }
+        #[test]
+        fn instantiate_msg_with_invalid_claim_condition_error() {
+            let msg = InstantiateMsg {
+                admin: "admin".into(),
+                poap_contract_code_id: 1,
+                poap_instantiate_msg: get_poap_instantiate_msg(),
+                claim_conditions: vec![ClaimCondition::DtagMatches {
+                    pattern: " ".into(),
+                }],
+            };
+            assert_eq!(
+                ContractError::InvalidDtagPattern {
+                    pattern: " ".into()
+                },
+                msg.validate().unwrap_err()
+            )
+        }
+        #[test]
+        fn valid_instantiate_msg_no_error() {
+            let msg = InstantiateMsg {
+                admin: "admin".into(),
+                poap_contract_code_id: 1,
+                poap_instantiate_msg: get_poap_instantiate_msg(),
+                claim_conditions: vec![ClaimCondition::HasProfile {}],
+            };
+            msg.validate().unwrap()
+        }
+    }
+    mod execute_msg {
+        use super::*;
+        #[test]
+        fn update_claim_conditions_with_invalid_subspace_id_error() {
+            let msg = ExecuteMsg::UpdateClaimConditions {
+                claim_conditions: vec![ClaimCondition::HasSubspacePermission {
+                    subspace_id: 0u64.into(),
+                    section_id: 0,
+                    permission: "SEND_POSTS".into(),
+                }],
+            };
+            assert_eq!(
+                msg.validate().unwrap_err(),
+                ContractError::InvalidSubspaceId {}
+            )
+        }
+        #[test]
+        fn other_msgs_no_error() {
+            let msg = ExecuteMsg::UpdateAdmin {
+                new_admin: "new_admin".into(),
+            };
+            msg.validate().unwrap()
+        }
+    }
+}
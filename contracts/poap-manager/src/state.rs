@@ -0,0 +1,18 @@
+use cosmwasm_schema::cw_serde;
+
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+
+use crate::msg::ClaimCondition;
+
+#[cw_serde]
+pub struct ConfigState {
+    pub admin: Addr,
+    pub poap_contract_code_id: u64,
+    /// Whether the users can claim the POAP.
+    pub is_mintable: bool,
+}
+
+pub const CONFIG: Item<ConfigState> = Item::new("config");
+pub const POAP_CONTRACT_ADDRESS: Item<Addr> = Item::new("poap_contract_address");
+pub const CLAIM_CONDITIONS: Item<Vec<ClaimCondition>> = Item::new("claim_conditions");
@@ -0,0 +1,160 @@
+use anyhow::{anyhow, Result as AnyResult};
+use cosmwasm_std::{to_binary, Addr, Api, Binary, BlockInfo, Empty, Querier, Storage, Uint64};
+use cw_multi_test::{AppResponse, Contract, ContractWrapper, CosmosRouter, Module};
+use desmos_bindings::{
+    mocks::mock_apps::DesmosModule,
+    msg::DesmosMsg,
+    profiles::{
+        mocks::mock_profiles_query_response,
+        models_app_links::{ApplicationLink, ApplicationLinkState, CallData, Data, OracleRequest},
+        models_query::QueryApplicationLinksResponse,
+        query::ProfilesQuery,
+    },
+    query::DesmosQuery,
+    subspaces::{models_query::QueryUserPermissionsResponse, query::SubspacesQuery},
+};
+use poap::InstantiateMsg as PoapInstantiateMsg;
+
+pub const ADMIN: &str = "cosmos17qcf9sv5yk0ly5vt3ztev70nwf6c5sprkwfh8t";
+/// User that satisfies all the claim conditions.
+pub const USER: &str = "desmos1nwp8gxrnmrsrzjdhvk47vvmthzxjtphgxp5ftc";
+/// User that doesn't have a Desmos profile.
+pub const USER_WITHOUT_PROFILE: &str = "desmos1rfv0f7mx7w9d3jv3h803u38vqym9ygg344asm3";
+pub const APPLICATION: &str = "twitter";
+pub const SUBSPACE_ID: Uint64 = Uint64::new(1);
+pub const SECTION_ID: u32 = 0;
+pub const PERMISSION: &str = "SEND_POSTS";
+
+/// Provides an instance of the POAP contract wired to its Desmos entry points.
+/// This instance can be used only during the integration tests.
+pub fn poap_contract() -> Box<dyn Contract<DesmosMsg, DesmosQuery>> {
+    let contract = ContractWrapper::new(
+        poap::desmos_entry::execute,
+        poap::desmos_entry::instantiate,
+        poap::desmos_entry::query,
+    )
+    .with_reply(poap::desmos_entry::reply);
+    Box::new(contract)
+}
+
+/// Provides a valid message to instantiate the POAP contract.
+pub fn get_poap_instantiate_msg() -> PoapInstantiateMsg {
+    PoapInstantiateMsg {
+        name: "test".into(),
+        symbol: "test".into(),
+        metadata_uri: "ipfs://poap.com/{token_id}".into(),
+        admin: None,
+        minter: None,
+        is_transferable: false,
+        is_mintable: true,
+        mint_start_time: None,
+        mint_end_time: None,
+        max_supply: None,
+        per_address_limit: None,
+        mint_price: None,
+        transfer_policy: None,
+        pauser: None,
+        announce: None,
+    }
+}
+
+fn mock_app_link(user: &Addr, application: &str) -> ApplicationLink {
+    ApplicationLink {
+        user: user.clone(),
+        data: Data {
+            application: application.to_string(),
+            username: "username".to_string(),
+        },
+        state: ApplicationLinkState::VerificationSuccess,
+        oracle_request: OracleRequest {
+            id: Uint64::new(0),
+            oracle_script_id: Uint64::new(0),
+            call_data: CallData {
+                application: application.to_string(),
+                call_data: "".to_string(),
+            },
+            client_id: "".to_string(),
+        },
+        result: None,
+        creation_time: "".to_string(),
+        expiration_time: "".to_string(),
+    }
+}
+
+/// Defines the mock keeper of Desmos modules.
+/// Only [`USER`] has a profile, a verified [`APPLICATION`] link and
+/// the [`PERMISSION`] inside the [`SUBSPACE_ID`] subspace.
+pub struct DesmosKeeper {}
+impl DesmosModule for DesmosKeeper {}
+impl Module for DesmosKeeper {
+    type ExecT = DesmosMsg;
+    type QueryT = DesmosQuery;
+    type SudoT = Empty;
+    fn execute<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _sender: Addr,
+        _msg: DesmosMsg,
+    ) -> AnyResult<AppResponse> {
+        unimplemented!()
+    }
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: DesmosQuery,
+    ) -> AnyResult<Binary> {
+        match request {
+            DesmosQuery::Profiles(query) => match &query {
+                ProfilesQuery::Profile { user } if user.as_str() == USER => {
+                    AnyResult::Ok(mock_profiles_query_response(&query).unwrap())
+                }
+                ProfilesQuery::Profile { .. } => Err(anyhow!("profile not found")),
+                ProfilesQuery::ApplicationLinks {
+                    user, application, ..
+                } => {
+                    let links = match (user, application) {
+                        (Some(user), Some(application)) if user.as_str() == USER => {
+                            vec![mock_app_link(user, application)]
+                        }
+                        _ => vec![],
+                    };
+                    AnyResult::Ok(to_binary(&QueryApplicationLinksResponse {
+                        links,
+                        pagination: None,
+                    })?)
+                }
+                _ => unimplemented!(),
+            },
+            DesmosQuery::Subspaces(SubspacesQuery::UserPermissions {
+                subspace_id, user, ..
+            }) => {
+                let permissions = if subspace_id == SUBSPACE_ID && user.as_str() == USER {
+                    vec![PERMISSION.to_string()]
+                } else {
+                    vec![]
+                };
+                AnyResult::Ok(to_binary(&QueryUserPermissionsResponse {
+                    permissions,
+                    details: vec![],
+                })?)
+            }
+            _ => unimplemented!(),
+        }
+    }
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Empty,
+    ) -> AnyResult<AppResponse> {
+        unimplemented!()
+    }
+}
@@ -1,19 +1,82 @@
-use crate::msg::MintStartEndTimeResponse;
+use crate::migrate::{
+    v1, V1_CW721_POAP_CONTRACT_NAME, V1_POAP_CONTRACT_NAME, V1_POAP_MANAGER_CONTRACT_NAME,
+};
+use crate::msg::{
+    AirdropFailure, AirdropFailuresResponse, AirdropJobResponse, AnnounceConfig, AnnounceResponse,
+    CollectedFeesResponse, ConfigResponse, EligibilityRule, EventInfo, EventResponse,
+    EventsResponse, HolderAtHeightResponse, HolderResponse, HoldersResponse, HookResponse,
+    HooksResponse, IsPausedResponse, MetadataUriResponse, MintInfo, MintStartEndTimeResponse,
+    MintStatus, MintToResponse, MintToResult, MintToStatus, MintVoucher, MintersResponse,
+    PoapHookMsg, PoapOfResponse, ReferralsResponse, RegisteredMinter, RemainingSupplyResponse,
+    RevocationInfo, RevocationResponse, TopReferrersResponse, TransferPolicy,
+    TransferPolicyResponse, VoucherSignDoc,
+};
 use crate::ContractError::{
-    InvalidTimestampValues, MintDisabled, MintTimeAlreadyEnded, MintTimeNotStarted,
-    MintUnauthorized, Ownership, TransferDisabled,
+    AirdropAlreadyProcessed, AirdropCancelled, AirdropFailed, AirdropNotFound, AllowlistNotSet,
+    AlreadyTransferred, AppLinkRequired, EventNotFound, HookNotFound, InsufficientCollectedFees,
+    InsufficientMintPrice, InvalidAirdropLimit, InvalidAnnounceText, InvalidClaimCode,
+    InvalidClaimCodeHash, InvalidImportLimit, InvalidMaxSupply, InvalidMerkleProof,
+    InvalidMerkleRoot, InvalidMintPrice, InvalidPerAddressLimit, InvalidTimestampValues,
+    InvalidVoucherSignature, InvalidVoucherSigner, MaxSupplyExceeded, MetadataFrozen, MintDisabled,
+    MintTimeAlreadyEnded, MintTimeNotStarted, MintUnauthorized, MinterNotFound,
+    MinterQuotaExceeded, NoV1TokensToImport, Ownership, PauseUnauthorized, Paused,
+    PoapAlreadyMinted, ProfileRequired, RecipientAlreadyHolder, RecipientNotAllowlisted,
+    ReferrerNotHolder, SelfReferral, SubspaceMembershipRequired, TransferDeadlineExpired,
+    TransferDisabled, UserGroupMembershipRequired, Version, VoucherAlreadyUsed, VoucherExpired,
+    VoucherSignerNotSet,
 };
 use crate::ExecuteMsg::{
-    Approve, ApproveAll, Burn, Mint, MintTo, Revoke, RevokeAll, SendNft, SetMintStartEndTime,
-    SetMintable, SetTransferable, TransferNft, UpdateMinter,
+    AddAirdropRecipients, AddClaimCodes, AddHook, AddMinter, Approve, ApproveAll, Burn,
+    CancelAirdrop, CreateAirdrop, CreateEvent, FreezeMetadata, ImportV1Tokens, Mint, MintTo,
+    MintWithCode, MintWithProof, MintWithVoucher, Pause, ProcessAirdrop, Recover, RemoveHook,
+    RemoveMinter, Revoke, RevokeAll, RevokeClaimCodes, RevokePoap, SendNft, SetAllowlist,
+    SetAnnounce, SetEligibilityRules, SetMaxSupply, SetMintPrice, SetMintStartEndTime, SetMintable,
+    SetPerAddressLimit, SetTransferPolicy, SetTransferable, TransferNft, Unpause, UpdateEvent,
+    UpdateMetadataUri, UpdateMinter, UpdatePauser, UpdateVoucherSigner, WithdrawFees,
+};
+use crate::{
+    desmos_entry, ContractError, ExecuteMsg, Extension, InstantiateMsg, MigrateMsg, PoapContract,
+    PoapExtension, QueryMsg,
+};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
 };
-use crate::{ContractError, ExecuteMsg, Extension, InstantiateMsg, PoapContract};
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{to_binary, Addr, CosmosMsg, Deps, DepsMut, Empty, Env, Timestamp, WasmMsg};
-use cw721::{Cw721Query, NftInfoResponse};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, to_vec, Addr, Attribute, BankMsg, Binary, ContractResult,
+    CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, OwnedDeps, QuerierResult, Reply, Response,
+    StdError, Storage, SubMsg, SubMsgResult, SystemError, SystemResult, Timestamp, Uint64, WasmMsg,
+    WasmQuery,
+};
+use cw2::VersionError;
+use cw721::{
+    AllNftInfoResponse, ContractInfoResponse, Cw721Query, Cw721QueryMsg, NftInfoResponse,
+    NumTokensResponse, OwnerOfResponse, TokensResponse,
+};
+use cw721_base::state::TokenInfo;
 use cw721_base::Cw721Contract;
 use cw_ownable::OwnershipError;
-use cw_utils::Expiration;
+use cw_storage_plus::Item;
+use cw_utils::{Expiration, PaymentError, Scheduled};
+use desmos_bindings::mocks::mock_queriers::mock_desmos_dependencies;
+use desmos_bindings::msg::DesmosMsg;
+use desmos_bindings::posts::models::{Entities, ReplySetting, TextTag};
+use desmos_bindings::posts::msg::PostsMsg;
+use desmos_bindings::profiles::mocks::mock_profiles_query_response;
+use desmos_bindings::profiles::models_app_links::{
+    ApplicationLink, ApplicationLinkState, CallData, Data, OracleRequest,
+};
+use desmos_bindings::profiles::models_query::QueryApplicationLinksResponse;
+use desmos_bindings::profiles::query::ProfilesQuery;
+use desmos_bindings::query::DesmosQuery;
+use desmos_bindings::subspaces::models_query::{
+    QueryUserGroupMembersResponse, QueryUserPermissionsResponse,
+};
+use desmos_bindings::subspaces::query::SubspacesQuery;
+use k256::ecdsa::signature::Signer;
+use k256::ecdsa::{Signature, SigningKey};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::marker::PhantomData;
 
 const ADMIN: &str = "admin";
 const MINTER: &str = "minter";
@@ -22,6 +85,8 @@ const CONTRACT: &str = "contract";
 const CONTRACT_NAME: &str = "Test POAP";
 const SYMBOL: &str = "TPOAP";
 const METADATA_URI: &str = "ipfs://poap-metadata";
+const V1_POAP: &str = "v1_poap";
+const V1_CW721_POAP: &str = "v1_cw721_poap";
 
 fn setup_contract(
     deps: DepsMut<'_>,
@@ -39,8 +104,14 @@ fn setup_contract(
         minter: Some(MINTER.to_string()),
         is_transferable,
         is_mintable,
-        mint_start_time,
-        mint_end_time,
+        mint_start_time: mint_start_time.map(Scheduled::AtTime),
+        mint_end_time: mint_end_time.map(Expiration::AtTime),
+        max_supply: None,
+        per_address_limit: None,
+        mint_price: None,
+        transfer_policy: None,
+        pauser: None,
+        announce: None,
     };
     let info = mock_info(ADMIN, &[]);
     let res = contract.instantiate(deps, mock_env(), info, msg).unwrap();
@@ -92,6 +163,12 @@ fn proper_instantiation_without_admin_and_minter() {
         is_mintable: true,
         mint_start_time: None,
         mint_end_time: None,
+        max_supply: None,
+        per_address_limit: None,
+        mint_price: None,
+        transfer_policy: None,
+        pauser: None,
+        announce: None,
     };
     let info = mock_info(ADMIN, &[]);
     contract
@@ -120,6 +197,12 @@ fn proper_instantiation_without_minter() {
         is_mintable: true,
         mint_start_time: None,
         mint_end_time: None,
+        max_supply: None,
+        per_address_limit: None,
+        mint_price: None,
+        transfer_policy: None,
+        pauser: None,
+        announce: None,
     };
     let info = mock_info(MINTER, &[]);
     contract
@@ -149,6 +232,12 @@ fn proper_instantiation() {
         is_mintable: true,
         mint_start_time: None,
         mint_end_time: None,
+        max_supply: None,
+        per_address_limit: None,
+        mint_price: None,
+        transfer_policy: None,
+        pauser: None,
+        announce: None,
     };
     let info = mock_info(ADMIN, &[]);
     contract
@@ -176,8 +265,14 @@ fn instantiate_with_invalid_times_fails() {
         minter: Some(MINTER.to_string()),
         is_transferable: false,
         is_mintable: true,
-        mint_start_time: Some(Timestamp::from_seconds(10)),
-        mint_end_time: Some(Timestamp::from_seconds(1)),
+        mint_start_time: Some(Scheduled::AtTime(Timestamp::from_seconds(10))),
+        mint_end_time: Some(Expiration::AtTime(Timestamp::from_seconds(1))),
+        max_supply: None,
+        per_address_limit: None,
+        mint_price: None,
+        transfer_policy: None,
+        pauser: None,
+        announce: None,
     };
 
     let err = contract
@@ -194,7 +289,16 @@ fn user_can_mint_if_mintable() {
 
     let info = mock_info(USER, &[]);
     let _ = contract
-        .execute(deps.as_mut(), mock_env(), info, Mint { extension: None })
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
         .unwrap();
 
     assert_poap_minted(&contract, deps.as_ref(), poap_id, USER.to_string());
@@ -212,7 +316,11 @@ fn user_cant_mint_if_not_mintable() {
             deps.as_mut(),
             mock_env(),
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap_err();
     assert_eq!(MintDisabled {}, err);
@@ -231,7 +339,11 @@ fn minter_can_mint_if_not_mintable() {
             deps.as_mut(),
             mock_env(),
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
 
@@ -251,7 +363,11 @@ fn admin_can_mint_if_not_mintable() {
             deps.as_mut(),
             mock_env(),
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
 
@@ -269,7 +385,11 @@ fn user_cant_mint_more_then_1_poap() {
             deps.as_mut(),
             mock_env(),
             mock_info(USER, &[]),
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id, USER.to_string());
@@ -279,7 +399,11 @@ fn user_cant_mint_more_then_1_poap() {
             deps.as_mut(),
             mock_env(),
             mock_info(USER, &[]),
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap_err();
     assert_eq!(
@@ -301,7 +425,11 @@ fn minter_cant_mint_more_then_1_poap() {
             deps.as_mut(),
             mock_env(),
             mock_info(MINTER, &[]),
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id, MINTER.to_string());
@@ -311,7 +439,11 @@ fn minter_cant_mint_more_then_1_poap() {
             deps.as_mut(),
             mock_env(),
             mock_info(MINTER, &[]),
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap_err();
     assert_eq!(
@@ -333,7 +465,11 @@ fn admin_cant_mint_more_then_1_poap() {
             deps.as_mut(),
             mock_env(),
             mock_info(ADMIN, &[]),
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id, ADMIN.to_string());
@@ -343,7 +479,11 @@ fn admin_cant_mint_more_then_1_poap() {
             deps.as_mut(),
             mock_env(),
             mock_info(ADMIN, &[]),
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap_err();
     assert_eq!(
@@ -369,7 +509,11 @@ fn user_can_mint_after_start_time() {
             deps.as_mut(),
             env_event_started,
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id, USER.to_string());
@@ -396,7 +540,11 @@ fn user_cant_mint_before_start_time() {
             deps.as_mut(),
             env_event_not_started,
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap_err();
     assert_eq!(MintTimeNotStarted {}, err);
@@ -422,7 +570,11 @@ fn minter_can_mint_before_start_time() {
             deps.as_mut(),
             env_event_not_started,
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
 
@@ -449,7 +601,11 @@ fn admin_can_mint_before_start_time() {
             deps.as_mut(),
             env_event_not_started,
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
 
@@ -476,7 +632,11 @@ fn user_can_mint_before_end_time() {
             deps.as_mut(),
             env_before_end_time,
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id, USER.to_string());
@@ -501,7 +661,11 @@ fn user_cant_mint_after_end_time() {
             deps.as_mut(),
             env_after_end_time,
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap_err();
     assert_eq!(MintTimeAlreadyEnded {}, err);
@@ -527,7 +691,11 @@ fn minter_can_mint_after_end_time() {
             deps.as_mut(),
             env_after_end_time,
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id, MINTER.to_string());
@@ -553,7 +721,11 @@ fn admin_can_mint_after_end_time() {
             deps.as_mut(),
             env_after_end_time,
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id, ADMIN.to_string());
@@ -579,7 +751,11 @@ fn user_can_mint_during_mint_time() {
             deps.as_mut(),
             mock_env_with_time(Timestamp::from_seconds(150)),
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
 
@@ -604,7 +780,11 @@ fn user_cant_mint_outside_mint_time() {
             deps.as_mut(),
             mock_env_with_time(Timestamp::from_seconds(99)),
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap_err();
     assert_eq!(MintTimeNotStarted {}, err);
@@ -616,7 +796,11 @@ fn user_cant_mint_outside_mint_time() {
             deps.as_mut(),
             mock_env_with_time(Timestamp::from_seconds(200)),
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap_err();
     assert_eq!(MintTimeAlreadyEnded {}, err);
@@ -641,7 +825,11 @@ fn minter_can_mint_outside_mint_time() {
             deps.as_mut(),
             mock_env_with_time(Timestamp::from_seconds(99)),
             user_info.clone(),
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(
@@ -668,7 +856,11 @@ fn minter_can_mint_outside_mint_time() {
             deps.as_mut(),
             mock_env_with_time(Timestamp::from_seconds(200)),
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id, MINTER.to_string());
@@ -693,7 +885,11 @@ fn admin_can_mint_outside_mint_time() {
             deps.as_mut(),
             mock_env_with_time(Timestamp::from_seconds(99)),
             user_info.clone(),
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id.clone(), ADMIN.to_string());
@@ -715,7 +911,11 @@ fn admin_can_mint_outside_mint_time() {
             deps.as_mut(),
             mock_env_with_time(Timestamp::from_seconds(200)),
             user_info,
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id, ADMIN.to_string());
@@ -732,8 +932,10 @@ fn user_cant_mint_for_other_users() {
             mock_env(),
             mock_info(USER, &[]),
             MintTo {
+                event_id: None,
                 users: vec![MINTER.to_string()],
                 extension: None,
+                skip_invalid: None,
             },
         )
         .unwrap_err();
@@ -752,8 +954,10 @@ fn minter_can_mint_for_other_users() {
             mock_env(),
             mock_info(MINTER, &[]),
             MintTo {
+                event_id: None,
                 users: vec![USER.to_string()],
                 extension: None,
+                skip_invalid: None,
             },
         )
         .unwrap();
@@ -772,8 +976,10 @@ fn admin_can_mint_for_other_users() {
             mock_env(),
             mock_info(ADMIN, &[]),
             MintTo {
+                event_id: None,
                 users: vec![USER.to_string()],
                 extension: None,
+                skip_invalid: None,
             },
         )
         .unwrap();
@@ -802,8 +1008,10 @@ fn admin_can_mint_for_other_users_if_minter_is_none() {
             mock_env(),
             mock_info(ADMIN, &[]),
             MintTo {
+                event_id: None,
                 users: vec![USER.to_string()],
                 extension: None,
+                skip_invalid: None,
             },
         )
         .unwrap();
@@ -822,8 +1030,10 @@ fn minter_cant_mint_more_than_one_poap_for_a_user() {
             mock_env(),
             mock_info(MINTER, &[]),
             MintTo {
+                event_id: None,
                 users: vec![USER.to_string()],
                 extension: None,
+                skip_invalid: None,
             },
         )
         .unwrap();
@@ -836,8 +1046,10 @@ fn minter_cant_mint_more_than_one_poap_for_a_user() {
             mock_env(),
             mock_info(MINTER, &[]),
             MintTo {
+                event_id: None,
                 users: vec![USER.to_string()],
                 extension: None,
+                skip_invalid: None,
             },
         )
         .unwrap_err();
@@ -861,8 +1073,10 @@ fn admin_cant_mint_more_than_one_poap_for_a_user() {
             mock_env(),
             mock_info(ADMIN, &[]),
             MintTo {
+                event_id: None,
                 users: vec![USER.to_string()],
                 extension: None,
+                skip_invalid: None,
             },
         )
         .unwrap();
@@ -875,8 +1089,10 @@ fn admin_cant_mint_more_than_one_poap_for_a_user() {
             mock_env(),
             mock_info(ADMIN, &[]),
             MintTo {
+                event_id: None,
                 users: vec![USER.to_string()],
                 extension: None,
+                skip_invalid: None,
             },
         )
         .unwrap_err();
@@ -897,7 +1113,16 @@ fn can_transfer_if_transferable() {
     let poap_id = contract.generate_poap_id(&deps.storage).unwrap();
     let info = mock_info(USER, &[]);
     let _ = contract
-        .execute(deps.as_mut(), mock_env(), info, Mint { extension: None })
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id.clone(), USER.to_string());
 
@@ -926,7 +1151,16 @@ fn cant_transfer_if_not_transferable() {
     let poap_id = contract.generate_poap_id(&deps.storage).unwrap();
     let info = mock_info(USER, &[]);
     let _ = contract
-        .execute(deps.as_mut(), mock_env(), info, Mint { extension: None })
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id.clone(), USER.to_string());
 
@@ -955,7 +1189,16 @@ fn can_send_if_transferable() {
     let poap_id = contract.generate_poap_id(&deps.storage).unwrap();
     let info = mock_info(USER, &[]);
     let _ = contract
-        .execute(deps.as_mut(), mock_env(), info, Mint { extension: None })
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id.clone(), USER.to_string());
 
@@ -994,7 +1237,11 @@ fn cant_send_if_not_transferable() {
             deps.as_mut(),
             mock_env(),
             info,
-            ExecuteMsg::Mint { extension: None },
+            ExecuteMsg::Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id.clone(), USER.to_string());
@@ -1159,8 +1406,8 @@ fn only_admin_can_set_start_end_time() {
             mock_env(),
             mock_info(USER, &[]),
             SetMintStartEndTime {
-                start_time: Some(Timestamp::from_seconds(1)),
-                end_time: Some(Timestamp::from_seconds(200)),
+                start_time: Some(Scheduled::AtTime(Timestamp::from_seconds(1))),
+                end_time: Some(Expiration::AtTime(Timestamp::from_seconds(200))),
             },
         )
         .unwrap_err();
@@ -1172,8 +1419,8 @@ fn only_admin_can_set_start_end_time() {
             mock_env(),
             mock_info(MINTER, &[]),
             SetMintStartEndTime {
-                start_time: Some(Timestamp::from_seconds(1)),
-                end_time: Some(Timestamp::from_seconds(200)),
+                start_time: Some(Scheduled::AtTime(Timestamp::from_seconds(1))),
+                end_time: Some(Expiration::AtTime(Timestamp::from_seconds(200))),
             },
         )
         .unwrap_err();
@@ -1185,8 +1432,8 @@ fn only_admin_can_set_start_end_time() {
             mock_env(),
             mock_info(ADMIN, &[]),
             SetMintStartEndTime {
-                start_time: Some(Timestamp::from_seconds(1)),
-                end_time: Some(Timestamp::from_seconds(200)),
+                start_time: Some(Scheduled::AtTime(Timestamp::from_seconds(1))),
+                end_time: Some(Expiration::AtTime(Timestamp::from_seconds(200))),
             },
         )
         .unwrap();
@@ -1196,8 +1443,8 @@ fn only_admin_can_set_start_end_time() {
         .unwrap();
     assert_eq!(
         MintStartEndTimeResponse {
-            start_time: Some(Timestamp::from_seconds(1)),
-            end_time: Some(Timestamp::from_seconds(200))
+            start_time: Some(Scheduled::AtTime(Timestamp::from_seconds(1))),
+            end_time: Some(Expiration::AtTime(Timestamp::from_seconds(200)))
         },
         start_end_time
     );
@@ -1214,8 +1461,8 @@ fn start_time_cant_be_higher_equal_then_end_time() {
             mock_env(),
             mock_info(ADMIN, &[]),
             SetMintStartEndTime {
-                start_time: Some(Timestamp::from_seconds(200)),
-                end_time: Some(Timestamp::from_seconds(200)),
+                start_time: Some(Scheduled::AtTime(Timestamp::from_seconds(200))),
+                end_time: Some(Expiration::AtTime(Timestamp::from_seconds(200))),
             },
         )
         .unwrap_err();
@@ -1227,8 +1474,8 @@ fn start_time_cant_be_higher_equal_then_end_time() {
             mock_env(),
             mock_info(ADMIN, &[]),
             SetMintStartEndTime {
-                start_time: Some(Timestamp::from_seconds(201)),
-                end_time: Some(Timestamp::from_seconds(200)),
+                start_time: Some(Scheduled::AtTime(Timestamp::from_seconds(201))),
+                end_time: Some(Expiration::AtTime(Timestamp::from_seconds(200))),
             },
         )
         .unwrap_err();
@@ -1247,7 +1494,11 @@ fn can_burn_their_poap() {
             deps.as_mut(),
             mock_env(),
             mock_info(USER, &[]),
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id.clone(), USER.to_string());
@@ -1267,6 +1518,94 @@ fn can_burn_their_poap() {
     assert_eq!(0, token_count);
 }
 
+#[test]
+fn burned_poap_ids_are_not_reused() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    // Mint two poaps
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user2", &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+    assert_poap_minted(&contract, deps.as_ref(), "1".to_string(), USER.to_string());
+    assert_poap_minted(
+        &contract,
+        deps.as_ref(),
+        "2".to_string(),
+        "user2".to_string(),
+    );
+
+    // Burn the first poap
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Burn {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+
+    // Check that a new user can still mint a poap
+    assert_eq!("3", contract.generate_poap_id(&deps.storage).unwrap());
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user3", &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+    assert_poap_minted(
+        &contract,
+        deps.as_ref(),
+        "3".to_string(),
+        "user3".to_string(),
+    );
+
+    // Check that the user that burned their poap can mint a new one
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            MintTo {
+                event_id: None,
+                users: vec![USER.to_string()],
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap();
+    assert_poap_minted(&contract, deps.as_ref(), "4".to_string(), USER.to_string());
+}
+
 #[test]
 fn can_approve_and_revoke() {
     let mut deps = mock_dependencies();
@@ -1280,7 +1619,11 @@ fn can_approve_and_revoke() {
             deps.as_mut(),
             mock_env(),
             mock_info(USER, &[]),
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id.clone(), USER.to_string());
@@ -1326,7 +1669,11 @@ fn can_approve_all_and_revoke_all() {
             deps.as_mut(),
             mock_env(),
             mock_info(USER, &[]),
-            Mint { extension: None },
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
         )
         .unwrap();
     assert_poap_minted(&contract, deps.as_ref(), poap_id.clone(), USER.to_string());
@@ -1377,3 +1724,5554 @@ fn only_admin_can_initiate_the_ownership_transfer() {
         .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg)
         .unwrap();
 }
+
+fn mock_v1_poap_contracts(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+    let mut items = HashMap::<(String, String), Binary>::new();
+    items.insert(
+        (V1_POAP.to_string(), v1::CONFIG_KEY.to_string()),
+        to_binary(&mock_v1_poap_config()).unwrap(),
+    );
+    items.insert(
+        (V1_POAP.to_string(), v1::EVENT_INFO_KEY.to_string()),
+        to_binary(&mock_v1_poap_event_info(1)).unwrap(),
+    );
+    items.insert(
+        (V1_POAP.to_string(), v1::CW721_ADDRESS_KEY.to_string()),
+        to_binary(&Addr::unchecked(V1_CW721_POAP)).unwrap(),
+    );
+    items.insert(
+        (V1_CW721_POAP.to_string(), "nft_info".to_string()),
+        to_binary(&ContractInfoResponse {
+            name: CONTRACT_NAME.to_string(),
+            symbol: SYMBOL.to_string(),
+        })
+        .unwrap(),
+    );
+
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::Raw { contract_addr, key } => {
+            let key = String::from_utf8(key.to_vec()).unwrap();
+            let value = items
+                .get(&(contract_addr.clone(), key))
+                .cloned()
+                .unwrap_or_default();
+            SystemResult::Ok(ContractResult::Ok(value))
+        }
+        // The v1 cw721-poap contract holds a single POAP owned by USER.
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == V1_CW721_POAP => {
+            let response = match from_binary(msg).unwrap() {
+                Cw721QueryMsg::NumTokens {} => to_binary(&NumTokensResponse { count: 1 }),
+                Cw721QueryMsg::AllTokens { start_after, .. } => to_binary(&TokensResponse {
+                    tokens: match start_after {
+                        None => vec!["1".to_string()],
+                        Some(_) => vec![],
+                    },
+                }),
+                Cw721QueryMsg::AllNftInfo { .. } => to_binary(&AllNftInfoResponse::<Extension> {
+                    access: OwnerOfResponse {
+                        owner: USER.to_string(),
+                        approvals: vec![],
+                    },
+                    info: NftInfoResponse {
+                        token_uri: Some(METADATA_URI.to_string()),
+                        extension: None,
+                    },
+                }),
+                _ => unimplemented!(),
+            };
+            SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+        }
+        _ => SystemResult::Err(SystemError::UnsupportedRequest {
+            kind: "wasm".to_string(),
+        }),
+    });
+}
+
+fn mock_v1_poap_config() -> v1::PoapConfig {
+    v1::PoapConfig {
+        admin: Addr::unchecked(ADMIN),
+        minter: Addr::unchecked(MINTER),
+        mint_enabled: true,
+    }
+}
+
+fn mock_v1_poap_event_info(per_address_limit: u32) -> v1::PoapEventInfo {
+    v1::PoapEventInfo {
+        creator: Addr::unchecked(ADMIN),
+        start_time: Timestamp::from_seconds(100),
+        end_time: Timestamp::from_seconds(200),
+        per_address_limit,
+        poap_uri: METADATA_URI.to_string(),
+        cw721_code_id: 1,
+    }
+}
+
+fn assert_v1_event_migrated(contract: &PoapContract<Extension, Empty, Empty, Empty>, deps: Deps) {
+    let admin = Cw721Contract::<Extension, Empty, Empty, Empty>::ownership(deps).unwrap();
+    assert_eq!(Some(Addr::unchecked(ADMIN)), admin.owner);
+
+    let minter = contract.minter(deps, mock_env()).unwrap();
+    assert_eq!(Some(MINTER.to_string()), minter.minter);
+
+    let mint_time = contract.mint_start_end_time(deps, mock_env()).unwrap();
+    assert_eq!(
+        MintStartEndTimeResponse {
+            start_time: Some(Scheduled::AtTime(Timestamp::from_seconds(100))),
+            end_time: Some(Expiration::AtTime(Timestamp::from_seconds(200))),
+        },
+        mint_time
+    );
+
+    assert!(contract.is_mintable(deps, mock_env()).unwrap().is_mintable);
+    assert!(
+        contract
+            .is_transferable(deps, mock_env())
+            .unwrap()
+            .is_transferable
+    );
+    assert_eq!(
+        METADATA_URI.to_string(),
+        contract.metadata_uri.load(deps.storage).unwrap()
+    );
+
+    let contract_info = contract.cw721_base.contract_info(deps).unwrap();
+    assert_eq!(CONTRACT_NAME, contract_info.name);
+    assert_eq!(SYMBOL, contract_info.symbol);
+
+    let version = cw2::get_contract_version(deps.storage).unwrap();
+    assert_eq!(crate::CONTRACT_NAME, version.contract);
+    assert_eq!(crate::CONTRACT_VERSION, version.version);
+}
+
+#[test]
+fn can_migrate_from_older_version() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    cw2::set_contract_version(&mut deps.storage, crate::CONTRACT_NAME, "2.0.0").unwrap();
+
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+
+    let version = cw2::get_contract_version(&deps.storage).unwrap();
+    assert_eq!(crate::CONTRACT_VERSION, version.version);
+}
+
+#[test]
+fn cant_migrate_to_older_version() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    cw2::set_contract_version(&mut deps.storage, crate::CONTRACT_NAME, "99.0.0").unwrap();
+
+    let err = contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Std(StdError::generic_err(format!(
+            "Cannot migrate from newer version (99.0.0) to older ({})",
+            crate::CONTRACT_VERSION
+        ))),
+        err
+    );
+}
+
+#[test]
+fn cant_migrate_from_unknown_contract() {
+    let mut deps = mock_dependencies();
+    let contract = PoapContract::<Extension, Empty, Empty, Empty>::default();
+    cw2::set_contract_version(&mut deps.storage, "crates.io:cw20-base", "1.0.0").unwrap();
+
+    let err = contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap_err();
+    assert_eq!(
+        Version(VersionError::WrongContract {
+            expected: crate::CONTRACT_NAME.to_string(),
+            found: "crates.io:cw20-base".to_string(),
+        }),
+        err
+    );
+}
+
+#[test]
+fn can_migrate_from_v1_cw721_poap() {
+    let mut deps = mock_dependencies();
+    mock_v1_poap_contracts(&mut deps);
+    let contract = PoapContract::<Extension, Empty, Empty, Empty>::default();
+
+    // Store the v1 cw721-poap state
+    cw2::set_contract_version(&mut deps.storage, V1_CW721_POAP_CONTRACT_NAME, "1.0.0").unwrap();
+    Item::<Addr>::new(v1::CW721_MINTER_KEY)
+        .save(&mut deps.storage, &Addr::unchecked(V1_POAP))
+        .unwrap();
+    contract
+        .cw721_base
+        .contract_info
+        .save(
+            &mut deps.storage,
+            &ContractInfoResponse {
+                name: CONTRACT_NAME.to_string(),
+                symbol: SYMBOL.to_string(),
+            },
+        )
+        .unwrap();
+    contract
+        .cw721_base
+        .tokens
+        .save(
+            &mut deps.storage,
+            "1",
+            &TokenInfo {
+                owner: Addr::unchecked(USER),
+                approvals: vec![],
+                token_uri: Some(METADATA_URI.to_string()),
+                extension: None,
+            },
+        )
+        .unwrap();
+    contract
+        .cw721_base
+        .increment_tokens(&mut deps.storage)
+        .unwrap();
+
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+    assert_v1_event_migrated(&contract, deps.as_ref());
+
+    // Check that the POAPs minted with v1 have been preserved
+    assert_poap_minted(&contract, deps.as_ref(), "1".to_string(), USER.to_string());
+
+    // Check that the one POAP per user rule still applies to the v1 POAPs
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_time(Timestamp::from_seconds(150)),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::PoapAlreadyMinted {
+            user: USER.to_string()
+        },
+        err
+    );
+}
+
+#[test]
+fn can_migrate_from_v1_poap() {
+    let mut deps = mock_dependencies();
+    mock_v1_poap_contracts(&mut deps);
+    let contract = PoapContract::<Extension, Empty, Empty, Empty>::default();
+
+    // Store the v1 poap state
+    cw2::set_contract_version(&mut deps.storage, V1_POAP_CONTRACT_NAME, "1.0.0").unwrap();
+    Item::new(v1::CONFIG_KEY)
+        .save(&mut deps.storage, &mock_v1_poap_config())
+        .unwrap();
+    Item::new(v1::EVENT_INFO_KEY)
+        .save(&mut deps.storage, &mock_v1_poap_event_info(2))
+        .unwrap();
+    Item::new(v1::CW721_ADDRESS_KEY)
+        .save(&mut deps.storage, &Addr::unchecked(V1_CW721_POAP))
+        .unwrap();
+
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+    assert_v1_event_migrated(&contract, deps.as_ref());
+    assert_eq!(2, contract.per_address_limit(&deps.storage).unwrap());
+
+    // Check that the v1 items have been removed
+    assert!(Item::<v1::PoapConfig>::new(v1::CONFIG_KEY)
+        .may_load(&deps.storage)
+        .unwrap()
+        .is_none());
+
+    // Check that the ids of the POAPs minted with v1 are reserved before importing them
+    assert_eq!(2, contract.next_poap_id.load(&deps.storage).unwrap());
+    assert_eq!(0, contract.cw721_base.token_count(&deps.storage).unwrap());
+
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ImportV1Tokens { limit: 10 },
+        )
+        .unwrap();
+    assert_poap_minted(&contract, deps.as_ref(), "1".to_string(), USER.to_string());
+    assert!(contract
+        .v1_import
+        .may_load(&deps.storage)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn v1_tokens_are_imported_in_batches() {
+    let mut deps = mock_dependencies();
+    mock_v1_poap_contracts(&mut deps);
+    let contract = PoapContract::<Extension, Empty, Empty, Empty>::default();
+    cw2::set_contract_version(&mut deps.storage, V1_POAP_CONTRACT_NAME, "1.0.0").unwrap();
+    Item::new(v1::CONFIG_KEY)
+        .save(&mut deps.storage, &mock_v1_poap_config())
+        .unwrap();
+    Item::new(v1::EVENT_INFO_KEY)
+        .save(&mut deps.storage, &mock_v1_poap_event_info(1))
+        .unwrap();
+    Item::new(v1::CW721_ADDRESS_KEY)
+        .save(&mut deps.storage, &Addr::unchecked(V1_CW721_POAP))
+        .unwrap();
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            ImportV1Tokens { limit: 1 },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Ownership(OwnershipError::NotOwner), err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ImportV1Tokens { limit: 0 },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidImportLimit {}, err);
+
+    // A full batch doesn't complete the import, since more POAPs could be left.
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ImportV1Tokens { limit: 1 },
+        )
+        .unwrap();
+    assert!(response
+        .attributes
+        .contains(&Attribute::new("is_completed", "false")));
+    assert_poap_minted(&contract, deps.as_ref(), "1".to_string(), USER.to_string());
+    assert_eq!(
+        Some("1".to_string()),
+        contract.v1_import.load(&deps.storage).unwrap().start_after
+    );
+
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ImportV1Tokens { limit: 1 },
+        )
+        .unwrap();
+    assert!(response
+        .attributes
+        .contains(&Attribute::new("imported", "0")));
+    assert!(response
+        .attributes
+        .contains(&Attribute::new("is_completed", "true")));
+    assert_eq!(1, contract.cw721_base.token_count(&deps.storage).unwrap());
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ImportV1Tokens { limit: 1 },
+        )
+        .unwrap_err();
+    assert_eq!(NoV1TokensToImport {}, err);
+}
+
+#[test]
+fn v1_holders_are_counted_after_the_migration() {
+    let mut deps = mock_dependencies();
+    mock_v1_poap_contracts(&mut deps);
+    let contract = PoapContract::<Extension, Empty, Empty, Empty>::default();
+
+    // Store the v1 poap-manager state
+    cw2::set_contract_version(&mut deps.storage, V1_POAP_MANAGER_CONTRACT_NAME, "1.0.0").unwrap();
+    Item::new(v1::CONFIG_KEY)
+        .save(
+            &mut deps.storage,
+            &v1::PoapManagerConfig {
+                admin: Addr::unchecked(ADMIN),
+                poap_address: Addr::unchecked(V1_POAP),
+            },
+        )
+        .unwrap();
+
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ImportV1Tokens { limit: 10 },
+        )
+        .unwrap();
+
+    // Check that the v1 holder is included in the holder index
+    let holder = contract
+        .holder_at_height(deps.as_ref(), mock_env(), USER.to_string(), None)
+        .unwrap();
+    assert_eq!(1, holder.balance);
+
+    // Check that the v1 holder has already reached the per address limit
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_time(Timestamp::from_seconds(150)),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        PoapAlreadyMinted {
+            user: USER.to_string()
+        },
+        err
+    );
+}
+
+#[test]
+fn can_migrate_from_v1_poap_manager() {
+    let mut deps = mock_dependencies();
+    mock_v1_poap_contracts(&mut deps);
+    let contract = PoapContract::<Extension, Empty, Empty, Empty>::default();
+
+    // Store the v1 poap-manager state
+    cw2::set_contract_version(&mut deps.storage, V1_POAP_MANAGER_CONTRACT_NAME, "1.0.0").unwrap();
+    Item::new(v1::CONFIG_KEY)
+        .save(
+            &mut deps.storage,
+            &v1::PoapManagerConfig {
+                admin: Addr::unchecked(ADMIN),
+                poap_address: Addr::unchecked(V1_POAP),
+            },
+        )
+        .unwrap();
+
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+    assert_v1_event_migrated(&contract, deps.as_ref());
+}
+
+#[test]
+fn migration_initializes_the_poap_id_counter() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    // Mint two poaps and burn the first one
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            MintTo {
+                event_id: None,
+                users: vec![USER.to_string(), "user2".to_string()],
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Burn {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+
+    // Simulate a contract deployed before the poap id counter was introduced
+    contract.next_poap_id.remove(&mut deps.storage);
+    cw2::set_contract_version(&mut deps.storage, crate::CONTRACT_NAME, "2.0.0").unwrap();
+
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+
+    // Check that the id of the poap that is still owned is skipped
+    assert_eq!("3", contract.generate_poap_id(&deps.storage).unwrap());
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user3", &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+    assert_poap_minted(
+        &contract,
+        deps.as_ref(),
+        "3".to_string(),
+        "user3".to_string(),
+    );
+    assert_eq!(4, contract.next_poap_id.load(&deps.storage).unwrap());
+}
+
+fn claim_code_hash(code: &str) -> Binary {
+    Binary::from(Sha256::digest(code.as_bytes()).as_slice())
+}
+
+#[test]
+fn only_admin_can_add_claim_codes() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            AddClaimCodes {
+                code_hashes: vec![claim_code_hash("code1")],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(Ownership(OwnershipError::NotOwner), err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            AddClaimCodes {
+                code_hashes: vec![claim_code_hash("code1"), claim_code_hash("code2")],
+            },
+        )
+        .unwrap();
+
+    // Add an already registered code
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            AddClaimCodes {
+                code_hashes: vec![claim_code_hash("code2"), claim_code_hash("code3")],
+            },
+        )
+        .unwrap();
+
+    let count = contract
+        .query_claim_codes_count(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(3, count.count);
+}
+
+#[test]
+fn add_claim_codes_with_invalid_hash_fails() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            AddClaimCodes {
+                code_hashes: vec![Binary::from(b"code1".as_slice())],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidClaimCodeHash {}, err);
+}
+
+#[test]
+fn user_cant_mint_with_code_if_not_mintable() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, false, None, None);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            AddClaimCodes {
+                code_hashes: vec![claim_code_hash("code1")],
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            MintWithCode {
+                event_id: None,
+                code: "code1".to_string(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintDisabled {}, err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            MintWithCode {
+                event_id: Some(2),
+                code: "code1".to_string(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(EventNotFound { event_id: 2 }, err);
+
+    // Check that the code has not been consumed
+    let count = contract
+        .query_claim_codes_count(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(1, count.count);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetMintable { mintable: true },
+        )
+        .unwrap();
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            MintWithCode {
+                event_id: None,
+                code: "code1".to_string(),
+                extension: None,
+            },
+        )
+        .unwrap();
+    assert_poap_minted(&contract, deps.as_ref(), "1".to_string(), USER.to_string());
+
+    let count = contract
+        .query_claim_codes_count(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(0, count.count);
+}
+
+#[test]
+fn claim_code_cant_be_used_twice() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            AddClaimCodes {
+                code_hashes: vec![claim_code_hash("code1")],
+            },
+        )
+        .unwrap();
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            MintWithCode {
+                event_id: None,
+                code: "code1".to_string(),
+                extension: None,
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user2", &[]),
+            MintWithCode {
+                event_id: None,
+                code: "code1".to_string(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidClaimCode {}, err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user2", &[]),
+            MintWithCode {
+                event_id: None,
+                code: "unknown".to_string(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidClaimCode {}, err);
+}
+
+#[test]
+fn revoked_claim_code_cant_be_used() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            AddClaimCodes {
+                code_hashes: vec![claim_code_hash("code1"), claim_code_hash("code2")],
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            RevokeClaimCodes {
+                code_hashes: vec![claim_code_hash("code1")],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(Ownership(OwnershipError::NotOwner), err);
+
+    // Revoke a registered code and an unknown one
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            RevokeClaimCodes {
+                code_hashes: vec![claim_code_hash("code1"), claim_code_hash("unknown")],
+            },
+        )
+        .unwrap();
+
+    let count = contract
+        .query_claim_codes_count(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(1, count.count);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            MintWithCode {
+                event_id: None,
+                code: "code1".to_string(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidClaimCode {}, err);
+}
+
+fn merkle_parent(left: &[u8], right: &[u8]) -> Vec<u8> {
+    let (left, right) = if left <= right {
+        (left, right)
+    } else {
+        (right, left)
+    };
+    Sha256::new()
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .to_vec()
+}
+
+/// Builds the allowlist Merkle tree of four users, returning the root and the users proofs.
+fn allowlist_tree(users: [&str; 4]) -> (Binary, Vec<Vec<Binary>>) {
+    let leaves: Vec<Vec<u8>> = users
+        .iter()
+        .map(|user| Sha256::digest(user.as_bytes()).to_vec())
+        .collect();
+    let nodes = [
+        merkle_parent(&leaves[0], &leaves[1]),
+        merkle_parent(&leaves[2], &leaves[3]),
+    ];
+    let root = Binary::from(merkle_parent(&nodes[0], &nodes[1]));
+
+    let proofs = (0..4)
+        .map(|i| {
+            vec![
+                Binary::from(leaves[i ^ 1].clone()),
+                Binary::from(nodes[(i / 2) ^ 1].clone()),
+            ]
+        })
+        .collect();
+
+    (root, proofs)
+}
+
+#[test]
+fn only_admin_can_set_allowlist() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let (root, _) = allowlist_tree([USER, "user2", "user3", "user4"]);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            SetAllowlist {
+                merkle_root: Some(root.clone()),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(Ownership(OwnershipError::NotOwner), err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetAllowlist {
+                merkle_root: Some(Binary::from(b"root".as_slice())),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidMerkleRoot {}, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetAllowlist {
+                merkle_root: Some(root.clone()),
+            },
+        )
+        .unwrap();
+    let allowlist = contract.allowlist(deps.as_ref(), mock_env()).unwrap();
+    assert_eq!(Some(root), allowlist.merkle_root);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetAllowlist { merkle_root: None },
+        )
+        .unwrap();
+    let allowlist = contract.allowlist(deps.as_ref(), mock_env()).unwrap();
+    assert_eq!(None, allowlist.merkle_root);
+}
+
+#[test]
+fn allowlisted_user_can_mint_with_proof_only_if_mintable() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, false, None, None);
+    let (root, proofs) = allowlist_tree(["user1", USER, "user3", "user4"]);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetAllowlist {
+                merkle_root: Some(root),
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            MintWithProof {
+                event_id: None,
+                proof: proofs[1].clone(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintDisabled {}, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetMintable { mintable: true },
+        )
+        .unwrap();
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            MintWithProof {
+                event_id: None,
+                proof: proofs[1].clone(),
+                extension: None,
+            },
+        )
+        .unwrap();
+    assert_poap_minted(&contract, deps.as_ref(), "1".to_string(), USER.to_string());
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user4", &[]),
+            MintWithProof {
+                event_id: None,
+                proof: proofs[3].clone(),
+                extension: None,
+            },
+        )
+        .unwrap();
+    assert_poap_minted(
+        &contract,
+        deps.as_ref(),
+        "2".to_string(),
+        "user4".to_string(),
+    );
+}
+
+#[test]
+fn user_cant_mint_with_invalid_proof() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let (root, proofs) = allowlist_tree(["user1", "user2", "user3", "user4"]);
+
+    // Mint without an allowlist
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user1", &[]),
+            MintWithProof {
+                event_id: None,
+                proof: proofs[0].clone(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(AllowlistNotSet {}, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetAllowlist {
+                merkle_root: Some(root),
+            },
+        )
+        .unwrap();
+
+    // Mint with the proof of another user
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            MintWithProof {
+                event_id: None,
+                proof: proofs[0].clone(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        InvalidMerkleProof {
+            user: USER.to_string()
+        },
+        err
+    );
+
+    // Mint with an incomplete proof
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user1", &[]),
+            MintWithProof {
+                event_id: None,
+                proof: proofs[0][..1].to_vec(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        InvalidMerkleProof {
+            user: "user1".to_string()
+        },
+        err
+    );
+}
+
+#[test]
+fn allowlisted_user_cant_mint_with_proof_outside_mint_time() {
+    let mut deps = mock_dependencies();
+    let start = Timestamp::from_seconds(1000);
+    let end = Timestamp::from_seconds(2000);
+    let contract = setup_contract(deps.as_mut(), true, true, Some(start), Some(end));
+    let (root, proofs) = allowlist_tree([USER, "user2", "user3", "user4"]);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetAllowlist {
+                merkle_root: Some(root),
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_time(Timestamp::from_seconds(500)),
+            mock_info(USER, &[]),
+            MintWithProof {
+                event_id: None,
+                proof: proofs[0].clone(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintTimeNotStarted {}, err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_time(end),
+            mock_info(USER, &[]),
+            MintWithProof {
+                event_id: None,
+                proof: proofs[0].clone(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintTimeAlreadyEnded {}, err);
+}
+
+fn voucher_signing_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32]).unwrap()
+}
+
+fn voucher_signer() -> Binary {
+    let public_key = voucher_signing_key().verifying_key().to_bytes();
+    Binary::from(public_key.as_slice())
+}
+
+fn sign_voucher(env: &Env, voucher: &MintVoucher) -> Binary {
+    let sign_doc = VoucherSignDoc {
+        contract_address: env.contract.address.to_string(),
+        recipient: voucher.recipient.clone(),
+        event_id: voucher.event_id,
+        expires_at: voucher.expires_at,
+        nonce: voucher.nonce,
+    };
+    let signature: Signature = voucher_signing_key().sign(&to_vec(&sign_doc).unwrap());
+    Binary::from(signature.as_ref())
+}
+
+fn setup_voucher_signer(
+    contract: &PoapContract<Extension, Empty, Empty, Empty>,
+    deps: DepsMut<'_>,
+) {
+    let _ = contract
+        .execute(
+            deps,
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            UpdateVoucherSigner {
+                public_key: Some(voucher_signer()),
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn only_admin_can_update_voucher_signer() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            UpdateVoucherSigner {
+                public_key: Some(voucher_signer()),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(Ownership(OwnershipError::NotOwner), err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            UpdateVoucherSigner {
+                public_key: Some(Binary::from(b"key".as_slice())),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidVoucherSigner {}, err);
+
+    setup_voucher_signer(&contract, deps.as_mut());
+    let signer = contract.voucher_signer(deps.as_ref(), mock_env()).unwrap();
+    assert_eq!(Some(voucher_signer()), signer.public_key);
+}
+
+#[test]
+fn user_can_mint_with_voucher() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    setup_voucher_signer(&contract, deps.as_mut());
+
+    let env = mock_env();
+    let voucher = MintVoucher {
+        recipient: USER.to_string(),
+        event_id: None,
+        expires_at: env.block.time.plus_seconds(60),
+        nonce: 1,
+    };
+    let signature = sign_voucher(&env, &voucher);
+
+    // The voucher can be submitted from any user
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(MINTER, &[]),
+            MintWithVoucher {
+                voucher: voucher.clone(),
+                signature: signature.clone(),
+                extension: None,
+            },
+        )
+        .unwrap();
+    assert_poap_minted(&contract, deps.as_ref(), "1".to_string(), USER.to_string());
+
+    // The voucher can't be used twice
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            MintWithVoucher {
+                voucher,
+                signature,
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(VoucherAlreadyUsed { nonce: 1 }, err);
+}
+
+#[test]
+fn user_can_mint_event_poap_with_voucher() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    setup_voucher_signer(&contract, deps.as_mut());
+    for is_mintable in [true, false] {
+        let _ = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(ADMIN, &[]),
+                create_event_msg(true, is_mintable),
+            )
+            .unwrap();
+    }
+
+    let env = mock_env();
+    let mint_with_voucher = |deps: DepsMut, event_id: u64, nonce: u64| {
+        let voucher = MintVoucher {
+            recipient: USER.to_string(),
+            event_id: Some(event_id),
+            expires_at: env.block.time.plus_seconds(60),
+            nonce,
+        };
+        contract.execute(
+            deps,
+            env.clone(),
+            mock_info(MINTER, &[]),
+            MintWithVoucher {
+                signature: sign_voucher(&env, &voucher),
+                voucher,
+                extension: None,
+            },
+        )
+    };
+
+    mint_with_voucher(deps.as_mut(), 1, 1).unwrap();
+    let tokens = contract
+        .event_tokens(deps.as_ref(), mock_env(), 1, None, None)
+        .unwrap();
+    assert_eq!(vec!["1".to_string()], tokens.tokens);
+
+    // The per address limit of the event still applies
+    let err = mint_with_voucher(deps.as_mut(), 1, 2).unwrap_err();
+    assert_eq!(
+        PoapAlreadyMinted {
+            user: USER.to_string()
+        },
+        err
+    );
+
+    // The event must be mintable
+    let err = mint_with_voucher(deps.as_mut(), 2, 3).unwrap_err();
+    assert_eq!(MintDisabled {}, err);
+}
+
+#[test]
+fn user_cant_mint_with_voucher_if_not_mintable() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, false, None, None);
+    setup_voucher_signer(&contract, deps.as_mut());
+
+    let env = mock_env();
+    let voucher = MintVoucher {
+        recipient: USER.to_string(),
+        event_id: None,
+        expires_at: env.block.time.plus_seconds(60),
+        nonce: 1,
+    };
+    let signature = sign_voucher(&env, &voucher);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            MintWithVoucher {
+                voucher,
+                signature,
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintDisabled {}, err);
+}
+
+#[test]
+fn user_cant_mint_with_expired_voucher() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    setup_voucher_signer(&contract, deps.as_mut());
+
+    let env = mock_env();
+    let voucher = MintVoucher {
+        recipient: USER.to_string(),
+        event_id: None,
+        expires_at: env.block.time,
+        nonce: 1,
+    };
+    let signature = sign_voucher(&env, &voucher);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            MintWithVoucher {
+                voucher,
+                signature,
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(VoucherExpired {}, err);
+}
+
+#[test]
+fn user_cant_mint_with_invalid_voucher() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let env = mock_env();
+    let voucher = MintVoucher {
+        recipient: USER.to_string(),
+        event_id: None,
+        expires_at: env.block.time.plus_seconds(60),
+        nonce: 1,
+    };
+    let signature = sign_voucher(&env, &voucher);
+
+    // Mint without a voucher signer
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER, &[]),
+            MintWithVoucher {
+                voucher: voucher.clone(),
+                signature: signature.clone(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(VoucherSignerNotSet {}, err);
+
+    setup_voucher_signer(&contract, deps.as_mut());
+
+    // Mint with a voucher issued to another user
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user2", &[]),
+            MintWithVoucher {
+                voucher: MintVoucher {
+                    recipient: "user2".to_string(),
+                    ..voucher.clone()
+                },
+                signature: signature.clone(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidVoucherSignature {}, err);
+
+    // Mint with a voucher issued for another event
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER, &[]),
+            MintWithVoucher {
+                voucher: MintVoucher {
+                    event_id: Some(1),
+                    ..voucher.clone()
+                },
+                signature: signature.clone(),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidVoucherSignature {}, err);
+
+    // Mint with a voucher issued for another contract
+    let mut other_env = env.clone();
+    other_env.contract.address = Addr::unchecked("other_contract");
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            MintWithVoucher {
+                signature: sign_voucher(&other_env, &voucher),
+                voucher,
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidVoucherSignature {}, err);
+}
+
+fn mock_desmos_deps(
+    handler: impl Fn(&DesmosQuery) -> QuerierResult + 'static,
+) -> OwnedDeps<MockStorage, MockApi, MockQuerier<DesmosQuery>, DesmosQuery> {
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: MockQuerier::<DesmosQuery>::new(&[]).with_custom_handler(handler),
+        custom_query_type: PhantomData,
+    }
+}
+
+fn setup_desmos_contract(mut deps: DepsMut<'_, DesmosQuery>, rules: Vec<EligibilityRule>) {
+    let msg = InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        metadata_uri: METADATA_URI.to_string(),
+        admin: Some(ADMIN.to_string()),
+        minter: Some(MINTER.to_string()),
+        is_transferable: true,
+        is_mintable: true,
+        mint_start_time: None,
+        mint_end_time: None,
+        max_supply: None,
+        per_address_limit: None,
+        mint_price: None,
+        transfer_policy: None,
+        pauser: None,
+        announce: None,
+    };
+    desmos_entry::instantiate(deps.branch(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+    desmos_entry::execute(
+        deps,
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        SetEligibilityRules { rules },
+    )
+    .unwrap();
+}
+
+fn mock_app_link(user: &str, application: &str, state: ApplicationLinkState) -> ApplicationLink {
+    ApplicationLink {
+        user: Addr::unchecked(user),
+        data: Data {
+            application: application.to_string(),
+            username: "username".to_string(),
+        },
+        state,
+        oracle_request: OracleRequest {
+            id: Uint64::new(0),
+            oracle_script_id: Uint64::new(0),
+            call_data: CallData {
+                application: "".to_string(),
+                call_data: "".to_string(),
+            },
+            client_id: "".to_string(),
+        },
+        result: None,
+        creation_time: "".to_string(),
+        expiration_time: "".to_string(),
+    }
+}
+
+#[test]
+fn only_admin_can_set_eligibility_rules() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let rules = vec![
+        EligibilityRule::HasProfile {},
+        EligibilityRule::HasAppLink {
+            application: "twitter".to_string(),
+        },
+    ];
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            SetEligibilityRules {
+                rules: rules.clone(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(Ownership(OwnershipError::NotOwner), err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetEligibilityRules {
+                rules: rules.clone(),
+            },
+        )
+        .unwrap();
+
+    let response = contract
+        .query_eligibility_rules(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(rules, response.rules);
+}
+
+#[test]
+fn user_without_profile_cant_mint() {
+    let mut deps = mock_desmos_deps(|query| match query {
+        DesmosQuery::Profiles(ProfilesQuery::Profile { .. }) => {
+            SystemResult::Ok(ContractResult::Err("profile not found".to_string()))
+        }
+        _ => SystemResult::Err(SystemError::Unknown {}),
+    });
+    setup_desmos_contract(deps.as_mut(), vec![EligibilityRule::HasProfile {}]);
+
+    let err = desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        Mint {
+            event_id: None,
+            extension: None,
+            referrer: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        ProfileRequired {
+            user: USER.to_string()
+        },
+        err
+    );
+
+    // The minter is not subject to the eligibility rules
+    desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MINTER, &[]),
+        MintTo {
+            event_id: None,
+            users: vec![USER.to_string()],
+            extension: None,
+            skip_invalid: None,
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn profile_query_failures_are_not_hidden() {
+    let mut deps = mock_desmos_deps(|query| match query {
+        DesmosQuery::Profiles(ProfilesQuery::Profile { .. }) => {
+            SystemResult::Ok(ContractResult::Err("out of gas".to_string()))
+        }
+        _ => SystemResult::Err(SystemError::Unknown {}),
+    });
+    setup_desmos_contract(deps.as_mut(), vec![EligibilityRule::HasProfile {}]);
+
+    let err = desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        Mint {
+            event_id: None,
+            extension: None,
+            referrer: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::Std(StdError::generic_err("Querier contract error: out of gas")),
+        err
+    );
+}
+
+#[test]
+fn user_with_profile_can_mint() {
+    let mut deps = mock_desmos_deps(|query| match query {
+        DesmosQuery::Profiles(profiles_query) => {
+            SystemResult::Ok(mock_profiles_query_response(profiles_query))
+        }
+        _ => SystemResult::Err(SystemError::Unknown {}),
+    });
+    setup_desmos_contract(deps.as_mut(), vec![EligibilityRule::HasProfile {}]);
+
+    desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        Mint {
+            event_id: None,
+            extension: None,
+            referrer: None,
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn user_needs_verified_app_link_to_mint() {
+    let mut deps = mock_desmos_deps(|query| match query {
+        DesmosQuery::Profiles(ProfilesQuery::ApplicationLinks { user, .. }) => {
+            let user = user.clone().unwrap();
+            let state = if user == USER {
+                ApplicationLinkState::VerificationSuccess
+            } else {
+                ApplicationLinkState::VerificationStarted
+            };
+            let response = QueryApplicationLinksResponse {
+                links: vec![mock_app_link(user.as_str(), "twitter", state)],
+                pagination: None,
+            };
+            SystemResult::Ok(to_binary(&response).into())
+        }
+        _ => SystemResult::Err(SystemError::Unknown {}),
+    });
+    setup_desmos_contract(
+        deps.as_mut(),
+        vec![EligibilityRule::HasAppLink {
+            application: "twitter".to_string(),
+        }],
+    );
+
+    let err = desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user2", &[]),
+        Mint {
+            event_id: None,
+            extension: None,
+            referrer: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        AppLinkRequired {
+            user: "user2".to_string(),
+            application: "twitter".to_string(),
+        },
+        err
+    );
+
+    desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        Mint {
+            event_id: None,
+            extension: None,
+            referrer: None,
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn user_needs_user_group_membership_to_mint() {
+    let mut deps = mock_desmos_deps(|query| match query {
+        DesmosQuery::Subspaces(SubspacesQuery::UserGroupMembers { pagination, .. }) => {
+            // Only the page of a single member that starts from the user address is requested.
+            let pagination = pagination.clone().unwrap();
+            assert_eq!(Uint64::new(1), pagination.limit);
+            let start = pagination.key.unwrap();
+            let response = QueryUserGroupMembersResponse {
+                members: [USER, "user3"]
+                    .into_iter()
+                    .filter(|member| member.as_bytes() >= start.as_slice())
+                    .take(1)
+                    .map(Addr::unchecked)
+                    .collect(),
+                pagination: None,
+            };
+            SystemResult::Ok(to_binary(&response).into())
+        }
+        _ => SystemResult::Err(SystemError::Unknown {}),
+    });
+    setup_desmos_contract(
+        deps.as_mut(),
+        vec![EligibilityRule::UserGroupMember {
+            subspace_id: Uint64::new(1),
+            group_id: 2,
+        }],
+    );
+
+    let err = desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user2", &[]),
+        Mint {
+            event_id: None,
+            extension: None,
+            referrer: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        UserGroupMembershipRequired {
+            user: "user2".to_string(),
+            subspace_id: 1,
+            group_id: 2,
+        },
+        err
+    );
+
+    desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        Mint {
+            event_id: None,
+            extension: None,
+            referrer: None,
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn user_needs_subspace_membership_to_mint() {
+    let mut deps = mock_desmos_deps(|query| match query {
+        DesmosQuery::Subspaces(SubspacesQuery::UserPermissions {
+            subspace_id, user, ..
+        }) => {
+            let permissions = if subspace_id.u64() == 1 && user.as_str() == USER {
+                vec!["SEND_POSTS".to_string()]
+            } else {
+                vec![]
+            };
+            let response = QueryUserPermissionsResponse {
+                permissions,
+                details: vec![],
+            };
+            SystemResult::Ok(to_binary(&response).into())
+        }
+        _ => SystemResult::Err(SystemError::Unknown {}),
+    });
+    setup_desmos_contract(
+        deps.as_mut(),
+        vec![EligibilityRule::SubspaceMember {
+            subspace_id: Uint64::new(1),
+            permission: "SEND_POSTS".to_string(),
+        }],
+    );
+
+    let err = desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user2", &[]),
+        Mint {
+            event_id: None,
+            extension: None,
+            referrer: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        SubspaceMembershipRequired {
+            user: "user2".to_string(),
+            subspace_id: 1,
+            permission: "SEND_POSTS".to_string(),
+        },
+        err
+    );
+
+    desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        Mint {
+            event_id: None,
+            extension: None,
+            referrer: None,
+        },
+    )
+    .unwrap();
+}
+
+fn setup_contract_with_limits(
+    deps: DepsMut<'_>,
+    max_supply: Option<u64>,
+    per_address_limit: Option<u32>,
+) -> PoapContract<'static, Extension, Empty, Empty, Empty> {
+    let contract = PoapContract::default();
+    let msg = InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        metadata_uri: METADATA_URI.to_string(),
+        admin: Some(ADMIN.to_string()),
+        minter: Some(MINTER.to_string()),
+        is_transferable: true,
+        is_mintable: true,
+        mint_start_time: None,
+        mint_end_time: None,
+        max_supply,
+        per_address_limit,
+        mint_price: None,
+        transfer_policy: None,
+        pauser: None,
+        announce: None,
+    };
+    contract
+        .instantiate(deps, mock_env(), mock_info(ADMIN, &[]), msg)
+        .unwrap();
+    contract
+}
+
+#[test]
+fn instantiate_with_zero_per_address_limit_fails() {
+    let mut deps = mock_dependencies();
+    let contract = PoapContract::<Extension, Empty, Empty, Empty>::default();
+    let msg = InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        metadata_uri: METADATA_URI.to_string(),
+        admin: Some(ADMIN.to_string()),
+        minter: Some(MINTER.to_string()),
+        is_transferable: true,
+        is_mintable: true,
+        mint_start_time: None,
+        mint_end_time: None,
+        max_supply: None,
+        per_address_limit: Some(0),
+        mint_price: None,
+        transfer_policy: None,
+        pauser: None,
+        announce: None,
+    };
+
+    let err = contract
+        .instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg)
+        .unwrap_err();
+    assert_eq!(InvalidPerAddressLimit {}, err);
+}
+
+#[test]
+fn user_cant_mint_over_max_supply() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract_with_limits(deps.as_mut(), Some(1), None);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user2", &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        MaxSupplyExceeded {
+            max_supply: 1,
+            remaining: 0
+        },
+        err
+    );
+
+    let supply = contract
+        .remaining_supply(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(
+        RemainingSupplyResponse {
+            max_supply: Some(1),
+            supply: 1,
+            remaining: Some(0),
+        },
+        supply
+    );
+}
+
+#[test]
+fn burned_poaps_count_towards_max_supply() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract_with_limits(deps.as_mut(), Some(1), None);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Burn {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+
+    // Check that burning the POAP doesn't free a slot of the max supply
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        MaxSupplyExceeded {
+            max_supply: 1,
+            remaining: 0
+        },
+        err
+    );
+
+    let supply = contract
+        .remaining_supply(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(Some(0), supply.remaining);
+}
+
+#[test]
+fn minter_cant_mint_batch_over_max_supply() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract_with_limits(deps.as_mut(), Some(2), None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            MintTo {
+                event_id: None,
+                users: vec![USER.to_string(), "user2".to_string(), "user3".to_string()],
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        MaxSupplyExceeded {
+            max_supply: 2,
+            remaining: 2
+        },
+        err
+    );
+
+    // Ensure that no POAP has been minted
+    let supply = contract
+        .remaining_supply(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(0, supply.supply);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            MintTo {
+                event_id: None,
+                users: vec![USER.to_string(), "user2".to_string()],
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap();
+    let supply = contract
+        .remaining_supply(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(Some(0), supply.remaining);
+}
+
+#[test]
+fn user_can_mint_up_to_per_address_limit() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract_with_limits(deps.as_mut(), None, Some(2));
+
+    for _ in 0..2 {
+        let _ = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(USER, &[]),
+                Mint {
+                    event_id: None,
+                    extension: None,
+                    referrer: None,
+                },
+            )
+            .unwrap();
+    }
+    assert_poap_minted(&contract, deps.as_ref(), "2".to_string(), USER.to_string());
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        PoapAlreadyMinted {
+            user: USER.to_string()
+        },
+        err
+    );
+}
+
+#[test]
+fn only_admin_can_set_max_supply() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract_with_limits(deps.as_mut(), None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            SetMaxSupply {
+                max_supply: Some(10),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(Ownership(OwnershipError::NotOwner), err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            MintTo {
+                event_id: None,
+                users: vec![USER.to_string(), "user2".to_string()],
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap();
+
+    // The max supply can't be lower than the current supply
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetMaxSupply {
+                max_supply: Some(1),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidMaxSupply { supply: 2 }, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetMaxSupply {
+                max_supply: Some(10),
+            },
+        )
+        .unwrap();
+    let supply = contract
+        .remaining_supply(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(Some(8), supply.remaining);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetMaxSupply { max_supply: None },
+        )
+        .unwrap();
+    let supply = contract
+        .remaining_supply(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(None, supply.remaining);
+}
+
+#[test]
+fn only_admin_can_set_per_address_limit() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract_with_limits(deps.as_mut(), None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            SetPerAddressLimit { limit: 3 },
+        )
+        .unwrap_err();
+    assert_eq!(Ownership(OwnershipError::NotOwner), err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetPerAddressLimit { limit: 0 },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidPerAddressLimit {}, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetPerAddressLimit { limit: 3 },
+        )
+        .unwrap();
+    assert_eq!(3, contract.per_address_limit(&deps.storage).unwrap());
+}
+
+const EVENT_METADATA_URI: &str = "ipfs://event-metadata";
+const EVENT_MINTER: &str = "event_minter";
+
+fn create_event_msg(is_transferable: bool, is_mintable: bool) -> ExecuteMsg<Extension, Empty> {
+    CreateEvent {
+        metadata_uri: EVENT_METADATA_URI.to_string(),
+        minter: Some(EVENT_MINTER.to_string()),
+        is_transferable,
+        is_mintable,
+        mint_start_time: None,
+        mint_end_time: None,
+    }
+}
+
+#[test]
+fn only_admin_can_create_event() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            create_event_msg(true, true),
+        )
+        .unwrap_err();
+    assert_eq!(Ownership(OwnershipError::NotOwner), err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            CreateEvent {
+                metadata_uri: EVENT_METADATA_URI.to_string(),
+                minter: None,
+                is_transferable: true,
+                is_mintable: true,
+                mint_start_time: Some(Scheduled::AtTime(Timestamp::from_seconds(200))),
+                mint_end_time: Some(Expiration::AtTime(Timestamp::from_seconds(100))),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidTimestampValues {}, err);
+
+    for _ in 0..2 {
+        let _ = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(ADMIN, &[]),
+                create_event_msg(true, true),
+            )
+            .unwrap();
+    }
+
+    let expected_info = EventInfo {
+        metadata_uri: EVENT_METADATA_URI.to_string(),
+        minter: Some(Addr::unchecked(EVENT_MINTER)),
+        is_transferable: true,
+        is_mintable: true,
+        mint_start_time: None,
+        mint_end_time: None,
+    };
+    let events = contract
+        .events(deps.as_ref(), mock_env(), None, None)
+        .unwrap();
+    assert_eq!(
+        EventsResponse {
+            events: vec![
+                EventResponse {
+                    event_id: 1,
+                    info: expected_info.clone(),
+                },
+                EventResponse {
+                    event_id: 2,
+                    info: expected_info,
+                },
+            ]
+        },
+        events
+    );
+
+    let events = contract
+        .events(deps.as_ref(), mock_env(), Some(1), None)
+        .unwrap();
+    assert_eq!(1, events.events.len());
+    assert_eq!(2, events.events[0].event_id);
+}
+
+#[test]
+fn only_admin_can_update_event() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            create_event_msg(true, true),
+        )
+        .unwrap();
+
+    let update_msg = |event_id| UpdateEvent {
+        event_id,
+        metadata_uri: "ipfs://updated-metadata".to_string(),
+        minter: None,
+        is_transferable: false,
+        is_mintable: false,
+        mint_start_time: Some(Scheduled::AtTime(Timestamp::from_seconds(100))),
+        mint_end_time: None,
+    };
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(EVENT_MINTER, &[]),
+            update_msg(1),
+        )
+        .unwrap_err();
+    assert_eq!(Ownership(OwnershipError::NotOwner), err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            update_msg(2),
+        )
+        .unwrap_err();
+    assert_eq!(EventNotFound { event_id: 2 }, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            update_msg(1),
+        )
+        .unwrap();
+    let event = contract.event(deps.as_ref(), mock_env(), 1).unwrap();
+    assert_eq!(
+        EventResponse {
+            event_id: 1,
+            info: EventInfo {
+                metadata_uri: "ipfs://updated-metadata".to_string(),
+                minter: None,
+                is_transferable: false,
+                is_mintable: false,
+                mint_start_time: Some(Scheduled::AtTime(Timestamp::from_seconds(100))),
+                mint_end_time: None,
+            },
+        },
+        event
+    );
+}
+
+#[test]
+fn user_can_mint_one_poap_per_event() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            create_event_msg(true, true),
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: Some(2),
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(EventNotFound { event_id: 2 }, err);
+
+    // Mint a POAP for the default event and one for the created event
+    for event_id in [None, Some(1)] {
+        let _ = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(USER, &[]),
+                Mint {
+                    event_id,
+                    extension: None,
+                    referrer: None,
+                },
+            )
+            .unwrap();
+    }
+    assert_poap_minted(&contract, deps.as_ref(), "1".to_string(), USER.to_string());
+    let poap_info = contract
+        .cw721_base
+        .nft_info(deps.as_ref(), "2".to_string())
+        .unwrap();
+    assert_eq!(Some(EVENT_METADATA_URI.to_string()), poap_info.token_uri);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: Some(1),
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        PoapAlreadyMinted {
+            user: USER.to_string()
+        },
+        err
+    );
+
+    let tokens = contract
+        .event_tokens(deps.as_ref(), mock_env(), 1, None, None)
+        .unwrap();
+    assert_eq!(vec!["2".to_string()], tokens.tokens);
+}
+
+#[test]
+fn only_event_minter_can_mint_to_if_event_not_mintable() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            create_event_msg(true, false),
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: Some(1),
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintDisabled {}, err);
+
+    // The contract minter is not allowed to mint the event POAPs
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            MintTo {
+                event_id: Some(1),
+                users: vec![USER.to_string()],
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintUnauthorized {}, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(EVENT_MINTER, &[]),
+            MintTo {
+                event_id: Some(1),
+                users: vec![USER.to_string(), "user2".to_string()],
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap();
+    let tokens = contract
+        .event_tokens(deps.as_ref(), mock_env(), 1, None, None)
+        .unwrap();
+    assert_eq!(vec!["1".to_string(), "2".to_string()], tokens.tokens);
+}
+
+#[test]
+fn event_poap_transfer_follows_event_config() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            create_event_msg(false, true),
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: Some(1),
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            TransferNft {
+                recipient: "user2".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(TransferDisabled {}, err);
+
+    // The event POAPs can still be burned
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Burn {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    let tokens = contract
+        .event_tokens(deps.as_ref(), mock_env(), 1, None, None)
+        .unwrap();
+    assert!(tokens.tokens.is_empty());
+}
+
+#[test]
+fn only_admin_can_update_metadata_uri() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+    let update_msg = UpdateMetadataUri {
+        metadata_uri: "ipfs://poap-metadata/{token_id}.json".to_string(),
+        refresh_token_ids: Some(vec!["1".to_string()]),
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            update_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(Ownership(OwnershipError::NotOwner), err);
+
+    let _ = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update_msg)
+        .unwrap();
+
+    // Check that the minted POAP has been refreshed
+    let poap_info = contract
+        .cw721_base
+        .nft_info(deps.as_ref(), "1".to_string())
+        .unwrap();
+    assert_eq!(
+        Some("ipfs://poap-metadata/1.json".to_string()),
+        poap_info.token_uri
+    );
+
+    // Check that the new POAPs use the template
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            MintTo {
+                event_id: None,
+                users: vec!["user2".to_string()],
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap();
+    let poap_info = contract
+        .cw721_base
+        .nft_info(deps.as_ref(), "2".to_string())
+        .unwrap();
+    assert_eq!(
+        Some("ipfs://poap-metadata/2.json".to_string()),
+        poap_info.token_uri
+    );
+}
+
+#[test]
+fn metadata_cant_be_updated_after_freeze() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            create_event_msg(true, true),
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            FreezeMetadata {},
+        )
+        .unwrap_err();
+    assert_eq!(Ownership(OwnershipError::NotOwner), err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            FreezeMetadata {},
+        )
+        .unwrap();
+    let response = contract
+        .query_metadata_uri(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(
+        MetadataUriResponse {
+            metadata_uri: METADATA_URI.to_string(),
+            is_frozen: true,
+        },
+        response
+    );
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            UpdateMetadataUri {
+                metadata_uri: "ipfs://new-metadata".to_string(),
+                refresh_token_ids: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MetadataFrozen {}, err);
+
+    let update_event_msg = |metadata_uri: &str| UpdateEvent {
+        event_id: 1,
+        metadata_uri: metadata_uri.to_string(),
+        minter: None,
+        is_transferable: true,
+        is_mintable: false,
+        mint_start_time: None,
+        mint_end_time: None,
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            update_event_msg("ipfs://new-metadata"),
+        )
+        .unwrap_err();
+    assert_eq!(MetadataFrozen {}, err);
+
+    // The other event fields can still be updated
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            update_event_msg(EVENT_METADATA_URI),
+        )
+        .unwrap();
+}
+
+fn mock_env_with_height(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+#[test]
+fn user_can_mint_only_during_mint_heights() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetMintStartEndTime {
+                start_time: Some(Scheduled::AtHeight(200)),
+                end_time: Some(Expiration::AtHeight(100)),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidTimestampValues {}, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetMintStartEndTime {
+                start_time: Some(Scheduled::AtHeight(100)),
+                end_time: Some(Expiration::AtHeight(200)),
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(99),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintTimeNotStarted {}, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(100),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+    assert_poap_minted(&contract, deps.as_ref(), "1".to_string(), USER.to_string());
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(200),
+            mock_info("user2", &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintTimeAlreadyEnded {}, err);
+}
+
+#[test]
+fn migration_converts_the_mint_timestamps() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    cw2::set_contract_version(&mut deps.storage, crate::CONTRACT_NAME, "2.0.0").unwrap();
+
+    // Store the mint time as the previous versions did
+    Item::<Option<Timestamp>>::new("mint_start_time")
+        .save(&mut deps.storage, &Some(Timestamp::from_seconds(100)))
+        .unwrap();
+    Item::<Option<Timestamp>>::new("mint_end_time")
+        .save(&mut deps.storage, &None)
+        .unwrap();
+
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+
+    let mint_time = contract
+        .mint_start_end_time(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(
+        MintStartEndTimeResponse {
+            start_time: Some(Scheduled::AtTime(Timestamp::from_seconds(100))),
+            end_time: None,
+        },
+        mint_time
+    );
+
+    // Migrating again keeps the converted values
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+    let migrated_again = contract
+        .mint_start_end_time(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(mint_time, migrated_again);
+}
+
+#[test]
+fn minter_can_skip_invalid_users() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+    let users = vec![
+        USER.to_string(),
+        "INVALID".to_string(),
+        "user2".to_string(),
+        "user2".to_string(),
+    ];
+
+    // Without skipping the invalid users the whole batch fails
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            MintTo {
+                event_id: None,
+                users: users.clone(),
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        PoapAlreadyMinted {
+            user: USER.to_string()
+        },
+        err
+    );
+
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            MintTo {
+                event_id: None,
+                users,
+                extension: None,
+                skip_invalid: Some(true),
+            },
+        )
+        .unwrap();
+    assert_poap_minted(
+        &contract,
+        deps.as_ref(),
+        "2".to_string(),
+        "user2".to_string(),
+    );
+
+    let data: MintToResponse = from_binary(&response.data.unwrap()).unwrap();
+    assert_eq!(
+        MintToResponse {
+            results: vec![
+                MintToResult {
+                    user: USER.to_string(),
+                    status: MintToStatus::AlreadyOwned {},
+                },
+                MintToResult {
+                    user: "INVALID".to_string(),
+                    status: MintToStatus::InvalidAddress {},
+                },
+                MintToResult {
+                    user: "user2".to_string(),
+                    status: MintToStatus::Minted {
+                        token_id: "2".to_string()
+                    },
+                },
+                MintToResult {
+                    user: "user2".to_string(),
+                    status: MintToStatus::AlreadyOwned {},
+                },
+            ]
+        },
+        data
+    );
+    assert!(response
+        .attributes
+        .contains(&Attribute::new("already_owned", "user, user2")));
+    assert!(response
+        .attributes
+        .contains(&Attribute::new("invalid_addresses", "INVALID")));
+}
+
+#[test]
+fn airdrop_is_processed_across_transactions() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            CreateAirdrop {
+                event_id: None,
+                recipients: vec!["user1".to_string(), USER.to_string()],
+                extension: None,
+            },
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            AddAirdropRecipients {
+                job_id: 1,
+                recipients: vec!["INVALID".to_string(), "user3".to_string()],
+            },
+        )
+        .unwrap();
+
+    // Anyone can process the airdrop
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ProcessAirdrop {
+                job_id: 1,
+                limit: 2,
+            },
+        )
+        .unwrap();
+    assert_poap_minted(
+        &contract,
+        deps.as_ref(),
+        "2".to_string(),
+        "user1".to_string(),
+    );
+    let job = contract.airdrop_job(deps.as_ref(), mock_env(), 1).unwrap();
+    assert_eq!(
+        AirdropJobResponse {
+            job_id: 1,
+            event_id: None,
+            total_recipients: 4,
+            processed: 2,
+            minted: 1,
+            failed: 1,
+            is_cancelled: false,
+            error: None,
+        },
+        job
+    );
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ProcessAirdrop {
+                job_id: 1,
+                limit: 10,
+            },
+        )
+        .unwrap();
+    assert_poap_minted(
+        &contract,
+        deps.as_ref(),
+        "3".to_string(),
+        "user3".to_string(),
+    );
+    let job = contract.airdrop_job(deps.as_ref(), mock_env(), 1).unwrap();
+    assert_eq!(4, job.processed);
+    assert_eq!(2, job.minted);
+    assert_eq!(2, job.failed);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ProcessAirdrop {
+                job_id: 1,
+                limit: 10,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(AirdropAlreadyProcessed { job_id: 1 }, err);
+
+    let failures = contract
+        .airdrop_failures(deps.as_ref(), mock_env(), 1, None, None)
+        .unwrap();
+    assert_eq!(
+        AirdropFailuresResponse {
+            failures: vec![
+                AirdropFailure {
+                    index: 1,
+                    user: USER.to_string(),
+                    status: MintToStatus::AlreadyOwned {},
+                },
+                AirdropFailure {
+                    index: 2,
+                    user: "INVALID".to_string(),
+                    status: MintToStatus::InvalidAddress {},
+                },
+            ]
+        },
+        failures
+    );
+}
+
+#[test]
+fn airdrop_is_stopped_when_max_supply_is_reached() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract_with_limits(deps.as_mut(), Some(1), None);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            CreateAirdrop {
+                event_id: None,
+                recipients: vec!["user1".to_string(), "user2".to_string()],
+                extension: None,
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ProcessAirdrop {
+                job_id: 1,
+                limit: 0,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidAirdropLimit {}, err);
+
+    // The POAPs minted before reaching the max supply are kept
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ProcessAirdrop {
+                job_id: 1,
+                limit: 10,
+            },
+        )
+        .unwrap();
+    assert_poap_minted(
+        &contract,
+        deps.as_ref(),
+        "1".to_string(),
+        "user1".to_string(),
+    );
+
+    let error = MaxSupplyExceeded {
+        max_supply: 1,
+        remaining: 0,
+    }
+    .to_string();
+    let job = contract.airdrop_job(deps.as_ref(), mock_env(), 1).unwrap();
+    assert_eq!(1, job.processed);
+    assert_eq!(1, job.minted);
+    assert_eq!(Some(error.clone()), job.error);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ProcessAirdrop {
+                job_id: 1,
+                limit: 10,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(AirdropFailed { job_id: 1, error }, err);
+}
+
+#[test]
+fn airdrop_quota_is_given_back_for_the_recipients_without_poap() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            AddMinter {
+                minter: "desk".to_string(),
+                quota: Some(4),
+                expiration: None,
+            },
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("desk", &[]),
+            CreateAirdrop {
+                event_id: None,
+                recipients: vec![
+                    "user1".to_string(),
+                    USER.to_string(),
+                    "INVALID".to_string(),
+                    "user3".to_string(),
+                ],
+                extension: None,
+            },
+        )
+        .unwrap();
+    let minted = |deps: Deps| {
+        contract
+            .registered_minters(deps, mock_env(), None, None)
+            .unwrap()
+            .minters[0]
+            .minted
+    };
+    assert_eq!(4, minted(deps.as_ref()));
+
+    // The other minters can't use the quota of the job creator
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            AddAirdropRecipients {
+                job_id: 1,
+                recipients: vec!["user4".to_string()],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintUnauthorized {}, err);
+
+    // The skipped recipients don't consume the quota
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ProcessAirdrop {
+                job_id: 1,
+                limit: 3,
+            },
+        )
+        .unwrap();
+    assert_eq!(2, minted(deps.as_ref()));
+    // The POAPs are minted by the job creator, not by who has processed the job
+    let poaps = contract
+        .poap_of(deps.as_ref(), mock_env(), "user1".to_string(), None, None)
+        .unwrap()
+        .poaps;
+    assert_eq!(
+        Some(Addr::unchecked("desk")),
+        poaps[0]
+            .mint_info
+            .as_ref()
+            .map(|info| info.minted_by.clone())
+    );
+
+    // The recipients that have not been processed don't consume the quota
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("desk", &[]),
+            CancelAirdrop { job_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(1, minted(deps.as_ref()));
+}
+
+#[test]
+fn only_minter_or_admin_can_manage_airdrop() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            CreateAirdrop {
+                event_id: None,
+                recipients: vec![USER.to_string()],
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintUnauthorized {}, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            CreateAirdrop {
+                event_id: None,
+                recipients: vec![USER.to_string()],
+                extension: None,
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            AddAirdropRecipients {
+                job_id: 1,
+                recipients: vec![USER.to_string()],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintUnauthorized {}, err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            CancelAirdrop { job_id: 1 },
+        )
+        .unwrap_err();
+    assert_eq!(MintUnauthorized {}, err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            CancelAirdrop { job_id: 2 },
+        )
+        .unwrap_err();
+    assert_eq!(AirdropNotFound { job_id: 2 }, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            CancelAirdrop { job_id: 1 },
+        )
+        .unwrap();
+
+    // A cancelled airdrop can't be processed anymore
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            ProcessAirdrop {
+                job_id: 1,
+                limit: 10,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(AirdropCancelled { job_id: 1 }, err);
+    let job = contract.airdrop_job(deps.as_ref(), mock_env(), 1).unwrap();
+    assert!(job.is_cancelled);
+    assert_eq!(0, job.processed);
+}
+
+#[test]
+fn holders_are_listed_with_mint_info() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    // POAP minted before the mint info was tracked.
+    contract
+        .cw721_base
+        .tokens
+        .save(
+            deps.as_mut().storage,
+            "1",
+            &TokenInfo {
+                owner: Addr::unchecked("legacy"),
+                approvals: vec![],
+                token_uri: Some(METADATA_URI.to_string()),
+                extension: None,
+            },
+        )
+        .unwrap();
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(100),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(200),
+            mock_info(MINTER, &[]),
+            MintTo {
+                event_id: None,
+                users: vec!["user2".to_string()],
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap();
+
+    let holders = contract
+        .holders(deps.as_ref(), mock_env(), None, Some(2))
+        .unwrap();
+    assert_eq!(
+        HoldersResponse {
+            holders: vec![
+                HolderResponse {
+                    owner: "legacy".to_string(),
+                    token_id: "1".to_string(),
+                    mint_info: None,
+                },
+                HolderResponse {
+                    owner: USER.to_string(),
+                    token_id: "2".to_string(),
+                    mint_info: Some(MintInfo {
+                        minted_at_height: 100,
+                        minted_at_time: mock_env().block.time,
+                        minted_by: Addr::unchecked(USER),
+                    }),
+                },
+            ]
+        },
+        holders
+    );
+
+    let holders = contract
+        .holders(deps.as_ref(), mock_env(), Some("2".to_string()), None)
+        .unwrap();
+    assert_eq!(
+        HoldersResponse {
+            holders: vec![HolderResponse {
+                owner: "user2".to_string(),
+                token_id: "3".to_string(),
+                mint_info: Some(MintInfo {
+                    minted_at_height: 200,
+                    minted_at_time: mock_env().block.time,
+                    minted_by: Addr::unchecked(MINTER),
+                }),
+            }]
+        },
+        holders
+    );
+}
+
+#[test]
+fn poap_of_returns_user_poaps() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let poaps = contract
+        .poap_of(deps.as_ref(), mock_env(), USER.to_string(), None, None)
+        .unwrap();
+    assert!(poaps.poaps.is_empty());
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(100),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+    let poaps = contract
+        .poap_of(deps.as_ref(), mock_env(), USER.to_string(), None, None)
+        .unwrap();
+    assert_eq!(
+        PoapOfResponse {
+            poaps: vec![HolderResponse {
+                owner: USER.to_string(),
+                token_id: "1".to_string(),
+                mint_info: Some(MintInfo {
+                    minted_at_height: 100,
+                    minted_at_time: mock_env().block.time,
+                    minted_by: Addr::unchecked(USER),
+                }),
+            }]
+        },
+        poaps
+    );
+
+    // The mint info is removed together with the burned POAP.
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Burn {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert!(!contract.mint_info.has(&deps.storage, "1"));
+    let poaps = contract
+        .poap_of(deps.as_ref(), mock_env(), USER.to_string(), None, None)
+        .unwrap();
+    assert!(poaps.poaps.is_empty());
+}
+
+#[test]
+fn poap_of_is_paginated() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetPerAddressLimit { limit: 3 },
+        )
+        .unwrap();
+    for _ in 0..3 {
+        let _ = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(MINTER, &[]),
+                MintTo {
+                    event_id: None,
+                    users: vec![USER.to_string()],
+                    extension: None,
+                    skip_invalid: None,
+                },
+            )
+            .unwrap();
+    }
+    let token_ids = |poaps: PoapOfResponse| {
+        poaps
+            .poaps
+            .into_iter()
+            .map(|poap| poap.token_id)
+            .collect::<Vec<_>>()
+    };
+
+    let poaps = contract
+        .poap_of(deps.as_ref(), mock_env(), USER.to_string(), None, Some(2))
+        .unwrap();
+    assert_eq!(vec!["1".to_string(), "2".to_string()], token_ids(poaps));
+
+    let poaps = contract
+        .poap_of(
+            deps.as_ref(),
+            mock_env(),
+            USER.to_string(),
+            Some("2".to_string()),
+            Some(2),
+        )
+        .unwrap();
+    assert_eq!(vec!["3".to_string()], token_ids(poaps));
+}
+
+#[test]
+fn admin_or_minter_can_recover_soulbound_poap() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), false, true, None, None);
+    for user in [USER, "user2"] {
+        let _ = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(user, &[]),
+                Mint {
+                    event_id: None,
+                    extension: None,
+                    referrer: None,
+                },
+            )
+            .unwrap();
+    }
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Recover {
+                token_id: "1".to_string(),
+                new_owner: "new_user".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintUnauthorized {}, err);
+
+    // The new owner can't receive a second POAP.
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            Recover {
+                token_id: "1".to_string(),
+                new_owner: "user2".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::PoapAlreadyMinted {
+            user: "user2".to_string()
+        },
+        err
+    );
+
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            Recover {
+                token_id: "1".to_string(),
+                new_owner: "new_user".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vec![
+            Attribute::new("action", "recover"),
+            Attribute::new("sender", MINTER),
+            Attribute::new("token_id", "1"),
+            Attribute::new("previous_owner", USER),
+            Attribute::new("new_owner", "new_user"),
+        ],
+        response.attributes
+    );
+    assert_poap_minted(
+        &contract,
+        deps.as_ref(),
+        "1".to_string(),
+        "new_user".to_string(),
+    );
+    let poaps = contract
+        .poap_of(deps.as_ref(), mock_env(), USER.to_string(), None, None)
+        .unwrap();
+    assert!(poaps.poaps.is_empty());
+}
+
+#[test]
+fn admin_or_minter_can_revoke_poap() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), false, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user2", &[]),
+            RevokePoap {
+                token_id: "1".to_string(),
+                reason: "fraud".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintUnauthorized {}, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            RevokePoap {
+                token_id: "1".to_string(),
+                reason: "fraud".to_string(),
+            },
+        )
+        .unwrap();
+
+    assert!(contract
+        .cw721_base
+        .nft_info(deps.as_ref(), "1".to_string())
+        .is_err());
+    assert_eq!(0, contract.cw721_base.token_count(&deps.storage).unwrap());
+    assert!(!contract.mint_info.has(&deps.storage, "1"));
+    let revocation = contract
+        .revocation(deps.as_ref(), mock_env(), "1".to_string())
+        .unwrap();
+    assert_eq!(
+        RevocationResponse {
+            token_id: "1".to_string(),
+            info: RevocationInfo {
+                owner: Addr::unchecked(USER),
+                reason: "fraud".to_string(),
+                revoked_by: Addr::unchecked(ADMIN),
+            }
+        },
+        revocation
+    );
+}
+
+#[test]
+fn only_admin_can_manage_minters() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            AddMinter {
+                minter: "desk".to_string(),
+                quota: None,
+                expiration: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Ownership(OwnershipError::NotOwner), err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            RemoveMinter {
+                minter: "desk".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        MinterNotFound {
+            minter: "desk".to_string()
+        },
+        err
+    );
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            AddMinter {
+                minter: "desk".to_string(),
+                quota: Some(5),
+                expiration: None,
+            },
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            RemoveMinter {
+                minter: "desk".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Ownership(OwnershipError::NotOwner), err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            RemoveMinter {
+                minter: "desk".to_string(),
+            },
+        )
+        .unwrap();
+    let minters = contract
+        .registered_minters(deps.as_ref(), mock_env(), None, None)
+        .unwrap();
+    assert!(minters.minters.is_empty());
+
+    // The legacy minter is not affected by the registry.
+    let minter = contract.minter(deps.as_ref(), mock_env()).unwrap();
+    assert_eq!(Some(MINTER.to_string()), minter.minter);
+}
+
+#[test]
+fn registered_minter_can_mint_within_quota() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            AddMinter {
+                minter: "desk".to_string(),
+                quota: Some(2),
+                expiration: None,
+            },
+        )
+        .unwrap();
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("desk", &[]),
+            MintTo {
+                event_id: None,
+                users: vec!["user1".to_string(), "user2".to_string()],
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap();
+    assert_poap_minted(
+        &contract,
+        deps.as_ref(),
+        "1".to_string(),
+        "user1".to_string(),
+    );
+
+    let minters = contract
+        .registered_minters(deps.as_ref(), mock_env(), None, None)
+        .unwrap();
+    assert_eq!(
+        MintersResponse {
+            minters: vec![RegisteredMinter {
+                minter: "desk".to_string(),
+                quota: Some(2),
+                minted: 2,
+                remaining_quota: Some(0),
+                expiration: None,
+            }]
+        },
+        minters
+    );
+
+    // Updating the minter keeps the number of minted POAPs.
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            AddMinter {
+                minter: "desk".to_string(),
+                quota: Some(3),
+                expiration: None,
+            },
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("desk", &[]),
+            CreateAirdrop {
+                event_id: None,
+                recipients: vec!["user3".to_string(), "user4".to_string()],
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        MinterQuotaExceeded {
+            minter: "desk".to_string(),
+            remaining: 1
+        },
+        err
+    );
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("desk", &[]),
+            MintTo {
+                event_id: None,
+                users: vec![
+                    "user3".to_string(),
+                    "user4".to_string(),
+                    "user5".to_string(),
+                ],
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        MinterQuotaExceeded {
+            minter: "desk".to_string(),
+            remaining: 1
+        },
+        err
+    );
+}
+
+#[test]
+fn expired_minter_can_not_mint() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            AddMinter {
+                minter: "desk".to_string(),
+                quota: None,
+                expiration: Some(Expiration::AtHeight(100)),
+            },
+        )
+        .unwrap();
+
+    let msg = MintTo {
+        event_id: None,
+        users: vec![USER.to_string()],
+        extension: None,
+        skip_invalid: None,
+    };
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(99),
+            mock_info("desk", &[]),
+            msg.clone(),
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(100),
+            mock_info("desk", &[]),
+            msg,
+        )
+        .unwrap_err();
+    assert_eq!(MintUnauthorized {}, err);
+    assert_eq!(
+        MintUnauthorized {},
+        contract
+            .assert_is_minter(
+                &deps.storage,
+                &mock_env_with_height(100).block,
+                &Addr::unchecked("desk")
+            )
+            .unwrap_err()
+    );
+    contract
+        .assert_is_minter(&deps.storage, &mock_env().block, &Addr::unchecked(MINTER))
+        .unwrap();
+}
+
+#[test]
+fn paid_mint_requires_mint_price_and_refunds_excess() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetMintPrice {
+                mint_price: vec![coin(0, "udsm")],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(InvalidMintPrice {}, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetMintPrice {
+                mint_price: vec![coin(100, "udsm")],
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vec![coin(100, "udsm")],
+        contract
+            .query_mint_price(deps.as_ref(), mock_env())
+            .unwrap()
+            .mint_price
+    );
+
+    let mint_msg = Mint {
+        event_id: None,
+        extension: None,
+        referrer: None,
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[coin(99, "udsm")]),
+            mint_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        InsufficientMintPrice {
+            mint_price: "100udsm".to_string()
+        },
+        err
+    );
+
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[coin(150, "udsm"), coin(5, "uatom")]),
+            mint_msg.clone(),
+        )
+        .unwrap();
+    assert_eq!(
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: USER.to_string(),
+            amount: vec![coin(50, "udsm"), coin(5, "uatom")],
+        })],
+        response.messages
+    );
+    assert_eq!(
+        CollectedFeesResponse {
+            fees: vec![coin(100, "udsm")]
+        },
+        contract
+            .query_collected_fees(deps.as_ref(), mock_env())
+            .unwrap()
+    );
+
+    // The minter doesn't pay the mint price and can't send funds.
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[coin(100, "udsm")]),
+            mint_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Payment(PaymentError::NonPayable {}), err);
+    let response = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg)
+        .unwrap();
+    assert!(response.messages.is_empty());
+}
+
+#[test]
+fn non_fee_mints_reject_funds() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[coin(100, "udsm")]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Payment(PaymentError::NonPayable {}), err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[coin(100, "udsm")]),
+            MintTo {
+                event_id: None,
+                users: vec![USER.to_string()],
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Payment(PaymentError::NonPayable {}), err);
+}
+
+#[test]
+fn only_admin_can_withdraw_fees() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetMintPrice {
+                mint_price: vec![coin(100, "udsm")],
+            },
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[coin(100, "udsm")]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            WithdrawFees {
+                receiver: USER.to_string(),
+                amount: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Ownership(OwnershipError::NotOwner), err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            WithdrawFees {
+                receiver: "treasury".to_string(),
+                amount: Some(vec![coin(101, "udsm")]),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        InsufficientCollectedFees {
+            collected: coin(100, "udsm")
+        },
+        err
+    );
+
+    // The coins with the same denom are checked together
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            WithdrawFees {
+                receiver: "treasury".to_string(),
+                amount: Some(vec![coin(60, "udsm"), coin(60, "udsm")]),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        InsufficientCollectedFees {
+            collected: coin(100, "udsm")
+        },
+        err
+    );
+
+    // The zero coins are not sent
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            WithdrawFees {
+                receiver: "treasury".to_string(),
+                amount: Some(vec![coin(0, "uatom"), coin(20, "udsm"), coin(20, "udsm")]),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "treasury".to_string(),
+            amount: vec![coin(40, "udsm")],
+        })],
+        response.messages
+    );
+
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            WithdrawFees {
+                receiver: "treasury".to_string(),
+                amount: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "treasury".to_string(),
+            amount: vec![coin(60, "udsm")],
+        })],
+        response.messages
+    );
+    assert!(contract
+        .query_collected_fees(deps.as_ref(), mock_env())
+        .unwrap()
+        .fees
+        .is_empty());
+}
+
+#[test]
+fn only_admin_can_manage_hooks() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            AddHook {
+                contract: "hook".to_string(),
+                ignore_errors: false,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Ownership(OwnershipError::NotOwner), err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            RemoveHook {
+                contract: "hook".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        HookNotFound {
+            contract: "hook".to_string()
+        },
+        err
+    );
+
+    for (hook, ignore_errors) in [("hook", false), ("hook2", true)] {
+        let _ = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(ADMIN, &[]),
+                AddHook {
+                    contract: hook.to_string(),
+                    ignore_errors,
+                },
+            )
+            .unwrap();
+    }
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            RemoveHook {
+                contract: "hook".to_string(),
+            },
+        )
+        .unwrap();
+
+    let hooks = contract.query_hooks(deps.as_ref(), mock_env()).unwrap();
+    assert_eq!(
+        HooksResponse {
+            hooks: vec![HookResponse {
+                contract: "hook2".to_string(),
+                ignore_errors: true,
+            }]
+        },
+        hooks
+    );
+}
+
+#[test]
+fn hooks_are_notified_on_mint_transfer_and_burn() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    for (hook, ignore_errors) in [("ignoring_hook", true), ("reverting_hook", false)] {
+        let _ = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(ADMIN, &[]),
+                AddHook {
+                    contract: hook.to_string(),
+                    ignore_errors,
+                },
+            )
+            .unwrap();
+    }
+    let hook_msgs = |msg: PoapHookMsg| {
+        vec![
+            SubMsg::reply_on_error(msg.clone().into_cosmos_msg("ignoring_hook").unwrap(), 1),
+            SubMsg::new(msg.into_cosmos_msg("reverting_hook").unwrap()),
+        ]
+    };
+
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        hook_msgs(PoapHookMsg::Minted {
+            token_id: "1".to_string(),
+            owner: USER.to_string(),
+            event_id: None,
+        }),
+        response.messages
+    );
+    assert_eq!(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reverting_hook".to_string(),
+            msg: Binary::from(
+                br#"{"poap_hook":{"minted":{"token_id":"1","owner":"user","event_id":null}}}"#
+                    .to_vec()
+            ),
+            funds: vec![],
+        }),
+        response.messages[1].msg
+    );
+
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            TransferNft {
+                recipient: "user2".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        hook_msgs(PoapHookMsg::Transferred {
+            token_id: "1".to_string(),
+            previous_owner: USER.to_string(),
+            owner: "user2".to_string(),
+            event_id: None,
+        }),
+        response.messages
+    );
+
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user2", &[]),
+            Burn {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        hook_msgs(PoapHookMsg::Burned {
+            token_id: "1".to_string(),
+            owner: "user2".to_string(),
+            event_id: None,
+        }),
+        response.messages
+    );
+}
+
+#[test]
+fn ignored_hook_failure_does_not_revert() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let response = contract
+        .reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: 1,
+                result: SubMsgResult::Err("hook failed".to_string()),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vec![
+            Attribute::new("action", "poap_hook_failed"),
+            Attribute::new("error", "hook failed"),
+        ],
+        response.attributes
+    );
+
+    contract
+        .reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: 2,
+                result: SubMsgResult::Err("hook failed".to_string()),
+            },
+        )
+        .unwrap_err();
+}
+
+/// Extension that handles the custom messages and rejects the mints to a banned user.
+#[derive(Default)]
+struct TestExtension;
+
+impl PoapExtension<Extension, Empty, Empty, Empty> for TestExtension {
+    fn execute(
+        &self,
+        _deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        _msg: Empty,
+    ) -> Result<Response, ContractError> {
+        Ok(Response::new()
+            .add_attribute("action", "custom_execute")
+            .add_attribute("sender", info.sender))
+    }
+
+    fn query(&self, _deps: Deps, _env: Env, _msg: Empty) -> Result<Binary, ContractError> {
+        Ok(to_binary("custom_query")?)
+    }
+
+    fn before_mint(
+        &self,
+        _storage: &dyn Storage,
+        owner: &Addr,
+        _event_id: Option<u64>,
+        _mint_info: &MintInfo,
+        _extension: &Extension,
+    ) -> Result<(), ContractError> {
+        if owner.as_str() == "banned" {
+            return Err(MintUnauthorized {});
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn extension_queries_are_rejected_by_default() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let err = contract
+        .query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Extension { msg: Empty {} },
+        )
+        .unwrap_err();
+    assert_eq!(
+        StdError::generic_err(ContractError::ExtensionQueryNotSupported {}.to_string()),
+        err
+    );
+}
+
+#[test]
+fn extension_handles_custom_messages() {
+    let mut deps = mock_dependencies();
+    let contract = PoapContract::<Extension, Empty, Empty, Empty, TestExtension>::default();
+    let msg = InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        metadata_uri: METADATA_URI.to_string(),
+        admin: Some(ADMIN.to_string()),
+        minter: Some(MINTER.to_string()),
+        is_transferable: true,
+        is_mintable: true,
+        mint_start_time: None,
+        mint_end_time: None,
+        max_supply: None,
+        per_address_limit: None,
+        mint_price: None,
+        transfer_policy: None,
+        pauser: None,
+        announce: None,
+    };
+    contract
+        .instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg)
+        .unwrap();
+
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            ExecuteMsg::Extension { msg: Empty {} },
+        )
+        .unwrap();
+    assert_eq!(
+        vec![
+            Attribute::new("action", "custom_execute"),
+            Attribute::new("sender", USER),
+        ],
+        response.attributes
+    );
+
+    let response = contract
+        .query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Extension { msg: Empty {} },
+        )
+        .unwrap();
+    assert_eq!("custom_query", from_binary::<String>(&response).unwrap());
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("banned", &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(MintUnauthorized {}, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn config_returns_contract_state_and_mint_status() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(
+        deps.as_mut(),
+        true,
+        true,
+        Some(Timestamp::from_seconds(100)),
+        Some(Timestamp::from_seconds(200)),
+    );
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_time(Timestamp::from_seconds(150)),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+    let config = contract
+        .config(
+            deps.as_ref(),
+            mock_env_with_time(Timestamp::from_seconds(150)),
+        )
+        .unwrap();
+    assert_eq!(
+        ConfigResponse {
+            name: CONTRACT_NAME.to_string(),
+            symbol: SYMBOL.to_string(),
+            admin: Some(ADMIN.to_string()),
+            minter: Some(MINTER.to_string()),
+            metadata_uri: METADATA_URI.to_string(),
+            is_transferable: true,
+            transfer_policy: None,
+            is_mintable: true,
+            mint_start_time: Some(Scheduled::AtTime(Timestamp::from_seconds(100))),
+            mint_end_time: Some(Expiration::AtTime(Timestamp::from_seconds(200))),
+            mint_status: MintStatus::Open,
+            num_tokens: 1,
+            max_supply: None,
+            per_address_limit: 1,
+            mint_price: vec![],
+            pauser: None,
+            is_paused: false,
+            announce: None,
+        },
+        config
+    );
+
+    let config = contract
+        .config(
+            deps.as_ref(),
+            mock_env_with_time(Timestamp::from_seconds(99)),
+        )
+        .unwrap();
+    assert_eq!(MintStatus::NotStarted, config.mint_status);
+
+    let config = contract
+        .config(
+            deps.as_ref(),
+            mock_env_with_time(Timestamp::from_seconds(200)),
+        )
+        .unwrap();
+    assert_eq!(MintStatus::Ended, config.mint_status);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetMaxSupply {
+                max_supply: Some(1),
+            },
+        )
+        .unwrap();
+    let config = contract
+        .config(
+            deps.as_ref(),
+            mock_env_with_time(Timestamp::from_seconds(150)),
+        )
+        .unwrap();
+    assert_eq!(MintStatus::SoldOut, config.mint_status);
+
+    let _ = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), Pause {})
+        .unwrap();
+    let config = contract
+        .config(
+            deps.as_ref(),
+            mock_env_with_time(Timestamp::from_seconds(150)),
+        )
+        .unwrap();
+    assert_eq!(MintStatus::Paused, config.mint_status);
+    let _ = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), Unpause {})
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetMaxSupply { max_supply: None },
+        )
+        .unwrap();
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetMintable { mintable: false },
+        )
+        .unwrap();
+    let config = contract
+        .config(
+            deps.as_ref(),
+            mock_env_with_time(Timestamp::from_seconds(150)),
+        )
+        .unwrap();
+    assert_eq!(MintStatus::Disabled, config.mint_status);
+}
+
+#[test]
+fn holder_index_tracks_balances_at_height() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    for user in [USER, "user2"] {
+        let _ = contract
+            .execute(
+                deps.as_mut(),
+                mock_env_with_height(100),
+                mock_info(user, &[]),
+                Mint {
+                    event_id: None,
+                    extension: None,
+                    referrer: None,
+                },
+            )
+            .unwrap();
+    }
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(200),
+            mock_info(USER, &[]),
+            TransferNft {
+                recipient: "user3".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(300),
+            mock_info("user2", &[]),
+            Burn {
+                token_id: "2".to_string(),
+            },
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(400),
+            mock_info(ADMIN, &[]),
+            Recover {
+                token_id: "1".to_string(),
+                new_owner: USER.to_string(),
+            },
+        )
+        .unwrap();
+
+    let balance_at = |user: &str, height: Option<u64>| {
+        contract
+            .holder_at_height(deps.as_ref(), mock_env(), user.to_string(), height)
+            .unwrap()
+    };
+    // The balances are the ones at the beginning of the requested block.
+    assert_eq!(
+        HolderAtHeightResponse {
+            balance: 0,
+            height: 100
+        },
+        balance_at(USER, Some(100))
+    );
+    assert_eq!(1, balance_at(USER, Some(101)).balance);
+    assert_eq!(0, balance_at(USER, Some(201)).balance);
+    assert_eq!(1, balance_at("user3", Some(201)).balance);
+    assert_eq!(1, balance_at("user2", Some(300)).balance);
+    assert_eq!(0, balance_at("user2", Some(301)).balance);
+    assert_eq!(0, balance_at("user3", Some(401)).balance);
+    assert_eq!(
+        HolderAtHeightResponse {
+            balance: 1,
+            height: mock_env().block.height
+        },
+        balance_at(USER, None)
+    );
+
+    let total_supply_at = |height: Option<u64>| {
+        contract
+            .total_supply_at_height(deps.as_ref(), mock_env(), height)
+            .unwrap()
+            .total_supply
+    };
+    assert_eq!(0, total_supply_at(Some(100)));
+    assert_eq!(2, total_supply_at(Some(101)));
+    assert_eq!(2, total_supply_at(Some(300)));
+    assert_eq!(1, total_supply_at(Some(301)));
+    assert_eq!(1, total_supply_at(None));
+}
+
+#[test]
+fn migration_seeds_the_holder_index() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(100),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+    cw2::set_contract_version(&mut deps.storage, crate::CONTRACT_NAME, "2.0.0").unwrap();
+
+    // Simulate a contract that didn't keep the holder index.
+    deps.storage.remove(b"total_supply");
+    deps.storage
+        .remove(&contract.holder_balances.key(&Addr::unchecked(USER)));
+
+    contract
+        .migrate(deps.as_mut(), mock_env_with_height(500), MigrateMsg {})
+        .unwrap();
+
+    let holder = contract
+        .holder_at_height(deps.as_ref(), mock_env(), USER.to_string(), Some(501))
+        .unwrap();
+    assert_eq!(1, holder.balance);
+    let total_supply = contract
+        .total_supply_at_height(deps.as_ref(), mock_env(), None)
+        .unwrap();
+    assert_eq!(1, total_supply.total_supply);
+}
+
+fn mint_poaps(
+    contract: &PoapContract<'static, Extension, Empty, Empty, Empty>,
+    deps: DepsMut<'_>,
+    users: &[&str],
+) {
+    let _ = contract
+        .execute(
+            deps,
+            mock_env(),
+            mock_info(MINTER, &[]),
+            MintTo {
+                event_id: None,
+                users: users.iter().map(|user| user.to_string()).collect(),
+                extension: None,
+                skip_invalid: None,
+            },
+        )
+        .unwrap();
+}
+
+fn set_transfer_policy(
+    contract: &PoapContract<'static, Extension, Empty, Empty, Empty>,
+    deps: DepsMut<'_>,
+    policy: TransferPolicy,
+) {
+    let _ = contract
+        .execute(
+            deps,
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetTransferPolicy {
+                policy: Some(policy),
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn only_admin_can_set_transfer_policy() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            SetTransferPolicy {
+                policy: Some(TransferPolicy::SingleTransfer {}),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Ownership(OwnershipError::NotOwner), err);
+
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetTransferPolicy {
+                policy: Some(TransferPolicy::AllowlistedContracts {
+                    contracts: vec!["escrow".to_string()],
+                }),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vec![
+            Attribute::new("action", "set_transfer_policy"),
+            Attribute::new("sender", ADMIN),
+            Attribute::new("transfer_policy", "allowlisted_contracts"),
+        ],
+        response.attributes
+    );
+    let policy = contract
+        .query_transfer_policy(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(
+        TransferPolicyResponse {
+            policy: Some(TransferPolicy::AllowlistedContracts {
+                contracts: vec!["escrow".to_string()],
+            }),
+        },
+        policy
+    );
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetTransferPolicy { policy: None },
+        )
+        .unwrap();
+    let policy = contract
+        .query_transfer_policy(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(None, policy.policy);
+}
+
+#[test]
+fn poaps_can_be_transferred_until_deadline() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    set_transfer_policy(
+        &contract,
+        deps.as_mut(),
+        TransferPolicy::Deadline {
+            deadline: Expiration::AtHeight(100),
+        },
+    );
+    mint_poaps(&contract, deps.as_mut(), &[USER]);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(99),
+            mock_info(USER, &[]),
+            TransferNft {
+                recipient: "user2".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(100),
+            mock_info("user2", &[]),
+            TransferNft {
+                recipient: USER.to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(TransferDeadlineExpired {}, err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env_with_height(100),
+            mock_info("user2", &[]),
+            ApproveAll {
+                operator: USER.to_string(),
+                expires: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(TransferDeadlineExpired {}, err);
+}
+
+#[test]
+fn single_transfer_policy_allows_only_one_transfer() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    set_transfer_policy(&contract, deps.as_mut(), TransferPolicy::SingleTransfer {});
+    mint_poaps(&contract, deps.as_mut(), &[USER]);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            TransferNft {
+                recipient: "user2".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user2", &[]),
+            TransferNft {
+                recipient: "user3".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        AlreadyTransferred {
+            token_id: "1".to_string()
+        },
+        err
+    );
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user2", &[]),
+            Approve {
+                spender: "user3".to_string(),
+                token_id: "1".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        AlreadyTransferred {
+            token_id: "1".to_string()
+        },
+        err
+    );
+}
+
+#[test]
+fn non_holders_only_policy_rejects_holders() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    set_transfer_policy(&contract, deps.as_mut(), TransferPolicy::NonHoldersOnly {});
+    mint_poaps(&contract, deps.as_mut(), &[USER, "user2"]);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            TransferNft {
+                recipient: "user2".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        RecipientAlreadyHolder {
+            recipient: "user2".to_string()
+        },
+        err
+    );
+
+    // A holder can still be approved to move the POAP to a non holder.
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Approve {
+                spender: "user2".to_string(),
+                token_id: "1".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user2", &[]),
+            TransferNft {
+                recipient: "user3".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_poap_minted(
+        &contract,
+        deps.as_ref(),
+        "1".to_string(),
+        "user3".to_string(),
+    );
+}
+
+#[test]
+fn allowlisted_contracts_policy_restricts_recipients_and_approvals() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    set_transfer_policy(
+        &contract,
+        deps.as_mut(),
+        TransferPolicy::AllowlistedContracts {
+            contracts: vec!["escrow".to_string()],
+        },
+    );
+    mint_poaps(&contract, deps.as_mut(), &[USER]);
+
+    let not_allowlisted = RecipientNotAllowlisted {
+        recipient: "user2".to_string(),
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            TransferNft {
+                recipient: "user2".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(not_allowlisted, err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Approve {
+                spender: "user2".to_string(),
+                token_id: "1".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(not_allowlisted, err);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            ApproveAll {
+                operator: "user2".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(not_allowlisted, err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Approve {
+                spender: "escrow".to_string(),
+                token_id: "1".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            SendNft {
+                contract: "escrow".to_string(),
+                token_id: "1".to_string(),
+                msg: Binary::default(),
+            },
+        )
+        .unwrap();
+    assert_poap_minted(
+        &contract,
+        deps.as_ref(),
+        "1".to_string(),
+        "escrow".to_string(),
+    );
+}
+
+#[test]
+fn only_admin_or_pauser_can_pause() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            UpdatePauser {
+                pauser: Some(USER.to_string()),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Ownership(OwnershipError::NotOwner), err);
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            UpdatePauser {
+                pauser: Some("pauser".to_string()),
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), Pause {})
+        .unwrap_err();
+    assert_eq!(PauseUnauthorized {}, err);
+
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("pauser", &[]),
+            Pause {},
+        )
+        .unwrap();
+    assert_eq!(
+        vec![
+            Attribute::new("action", "pause"),
+            Attribute::new("sender", "pauser"),
+        ],
+        response.attributes
+    );
+    let is_paused = contract.is_paused(deps.as_ref(), mock_env()).unwrap();
+    assert_eq!(IsPausedResponse { is_paused: true }, is_paused);
+
+    // The pauser can't resume the contract.
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("pauser", &[]),
+            Unpause {},
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Ownership(OwnershipError::NotOwner), err);
+
+    let _ = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), Unpause {})
+        .unwrap();
+    let is_paused = contract.is_paused(deps.as_ref(), mock_env()).unwrap();
+    assert_eq!(IsPausedResponse { is_paused: false }, is_paused);
+}
+
+#[test]
+fn paused_contract_rejects_state_changes() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    mint_poaps(&contract, deps.as_mut(), &[USER]);
+    let _ = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), Pause {})
+        .unwrap();
+
+    let paused_msgs: Vec<(&str, ExecuteMsg<Extension, Empty>)> = vec![
+        (
+            "user2",
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        ),
+        (
+            MINTER,
+            MintTo {
+                event_id: None,
+                users: vec!["user2".to_string()],
+                extension: None,
+                skip_invalid: None,
+            },
+        ),
+        (
+            USER,
+            TransferNft {
+                recipient: "user2".to_string(),
+                token_id: "1".to_string(),
+            },
+        ),
+        (
+            USER,
+            Approve {
+                spender: "user2".to_string(),
+                token_id: "1".to_string(),
+                expires: None,
+            },
+        ),
+        (
+            USER,
+            Burn {
+                token_id: "1".to_string(),
+            },
+        ),
+        (ADMIN, SetMintable { mintable: false }),
+    ];
+    for (sender, msg) in paused_msgs {
+        let err = contract
+            .execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), msg)
+            .unwrap_err();
+        assert_eq!(Paused {}, err);
+    }
+
+    // The ownership can still be managed.
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::UpdateOwnership(cw_ownable::Action::TransferOwnership {
+                new_owner: "new_admin".to_string(),
+                expiry: None,
+            }),
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("new_admin", &[]),
+            ExecuteMsg::UpdateOwnership(cw_ownable::Action::AcceptOwnership),
+        )
+        .unwrap();
+
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("new_admin", &[]),
+            Unpause {},
+        )
+        .unwrap();
+    mint_poaps(&contract, deps.as_mut(), &["user2"]);
+    assert_poap_minted(
+        &contract,
+        deps.as_ref(),
+        "2".to_string(),
+        "user2".to_string(),
+    );
+}
+
+fn announce_config() -> AnnounceConfig {
+    AnnounceConfig {
+        subspace_id: Uint64::new(1),
+        section_id: 0,
+        text: "Welcome {user}, here is your POAP #{token_id}!".to_string(),
+    }
+}
+
+fn announce_post(owner: &str, token_id: &str) -> SubMsg<DesmosMsg> {
+    SubMsg::new(CosmosMsg::Custom(DesmosMsg::from(PostsMsg::CreatePost {
+        subspace_id: Uint64::new(1),
+        section_id: 0,
+        external_id: None,
+        text: Some(format!("Welcome {owner}, here is your POAP #{token_id}!")),
+        entities: Some(Entities {
+            hashtags: vec![],
+            mentions: vec![TextTag {
+                start: Uint64::new(8),
+                end: Uint64::new(8 + owner.len() as u64 - 1),
+                tag: owner.to_string(),
+            }],
+            urls: vec![],
+        }),
+        tags: vec![],
+        attachments: None,
+        author: mock_env().contract.address,
+        conversation_id: None,
+        reply_settings: ReplySetting::Everyone,
+        referenced_posts: vec![],
+    })))
+}
+
+#[test]
+fn only_admin_can_set_announce() {
+    let mut deps = mock_desmos_dependencies();
+    setup_desmos_contract(deps.as_mut(), vec![]);
+
+    let err = desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        SetAnnounce {
+            announce: Some(announce_config()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Ownership(OwnershipError::NotOwner), err);
+
+    let err = desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        SetAnnounce {
+            announce: Some(AnnounceConfig {
+                text: " ".to_string(),
+                ..announce_config()
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(InvalidAnnounceText {}, err);
+
+    desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        SetAnnounce {
+            announce: Some(announce_config()),
+        },
+    )
+    .unwrap();
+    let announce: AnnounceResponse = from_binary(
+        &desmos_entry::query(deps.as_ref(), mock_env(), QueryMsg::Announce {}).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(Some(announce_config()), announce.announce);
+}
+
+#[test]
+fn desmos_entry_announces_mints_as_posts() {
+    let mut deps = mock_desmos_dependencies();
+    setup_desmos_contract(deps.as_mut(), vec![]);
+
+    // Without an announce configuration the mints are not announced.
+    let response = desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        Mint {
+            event_id: None,
+            extension: None,
+            referrer: None,
+        },
+    )
+    .unwrap();
+    assert!(response.messages.is_empty());
+
+    desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        SetAnnounce {
+            announce: Some(announce_config()),
+        },
+    )
+    .unwrap();
+
+    let response = desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user2", &[]),
+        Mint {
+            event_id: None,
+            extension: None,
+            referrer: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(vec![announce_post("user2", "2")], response.messages);
+
+    let response = desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MINTER, &[]),
+        MintTo {
+            event_id: None,
+            users: vec!["user3".to_string(), "user4".to_string()],
+            extension: None,
+            skip_invalid: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        vec![announce_post("user3", "3"), announce_post("user4", "4")],
+        response.messages
+    );
+
+    // The airdropped POAPs are announced too.
+    desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        CreateAirdrop {
+            event_id: None,
+            recipients: vec!["user5".to_string(), "user2".to_string()],
+            extension: None,
+        },
+    )
+    .unwrap();
+    let response = desmos_entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ProcessAirdrop {
+            job_id: 1,
+            limit: 2,
+        },
+    )
+    .unwrap();
+    assert_eq!(vec![announce_post("user5", "5")], response.messages);
+}
+
+#[test]
+fn default_entry_does_not_announce_mints() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            SetAnnounce {
+                announce: Some(announce_config()),
+            },
+        )
+        .unwrap();
+
+    let response = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+    assert!(response.messages.is_empty());
+}
+
+#[test]
+fn mint_with_referrer_tracks_referrals() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let mint = |deps: DepsMut, user: &str, referrer: Option<&str>| {
+        contract.execute(
+            deps,
+            mock_env(),
+            mock_info(user, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: referrer.map(str::to_string),
+            },
+        )
+    };
+
+    let response = mint(deps.as_mut(), USER, None).unwrap();
+    assert!(response
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "referrer" && attr.value == "none"));
+    let response = mint(deps.as_mut(), "user2", Some(USER)).unwrap();
+    assert!(response
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "referrer" && attr.value == USER));
+    mint(deps.as_mut(), "user3", Some(USER)).unwrap();
+    mint(deps.as_mut(), "user4", Some("user2")).unwrap();
+
+    let referrals = |referrer: &str| {
+        contract
+            .query_referrals(deps.as_ref(), mock_env(), referrer.to_string())
+            .unwrap()
+    };
+    assert_eq!(
+        ReferralsResponse {
+            referrer: USER.to_string(),
+            count: 2
+        },
+        referrals(USER)
+    );
+    assert_eq!(1, referrals("user2").count);
+    assert_eq!(0, referrals("user3").count);
+
+    let top_referrers = |start_after: Option<&str>, limit: Option<u32>| {
+        contract
+            .top_referrers(
+                deps.as_ref(),
+                mock_env(),
+                start_after.map(str::to_string),
+                limit,
+            )
+            .unwrap()
+    };
+    assert_eq!(
+        TopReferrersResponse {
+            referrers: vec![
+                ReferralsResponse {
+                    referrer: USER.to_string(),
+                    count: 2
+                },
+                ReferralsResponse {
+                    referrer: "user2".to_string(),
+                    count: 1
+                },
+            ]
+        },
+        top_referrers(None, None)
+    );
+    assert_eq!(
+        vec![USER.to_string()],
+        top_referrers(None, Some(1))
+            .referrers
+            .into_iter()
+            .map(|referrals| referrals.referrer)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec!["user2".to_string()],
+        top_referrers(Some(USER), None)
+            .referrers
+            .into_iter()
+            .map(|referrals| referrals.referrer)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn mint_rejects_invalid_referrers() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), true, true, None, None);
+    let mint = |deps: DepsMut, user: &str, referrer: &str| {
+        contract.execute(
+            deps,
+            mock_env(),
+            mock_info(user, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: Some(referrer.to_string()),
+            },
+        )
+    };
+
+    let err = mint(deps.as_mut(), USER, USER).unwrap_err();
+    assert_eq!(SelfReferral {}, err);
+
+    let err = mint(deps.as_mut(), USER, "user2").unwrap_err();
+    assert_eq!(
+        ReferrerNotHolder {
+            referrer: "user2".to_string()
+        },
+        err
+    );
+
+    // A referrer that doesn't own their POAP anymore can't refer other users.
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Mint {
+                event_id: None,
+                extension: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            Burn {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    let err = mint(deps.as_mut(), "user2", USER).unwrap_err();
+    assert_eq!(
+        ReferrerNotHolder {
+            referrer: USER.to_string()
+        },
+        err
+    );
+    assert_eq!(
+        0,
+        contract
+            .query_referrals(deps.as_ref(), mock_env(), USER.to_string())
+            .unwrap()
+            .count
+    );
+}
@@ -1,6 +1,7 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError, VerificationError};
 use cw721_base::ContractError as Cw721BaseContractError;
 use cw_ownable::OwnershipError;
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -14,6 +15,12 @@ pub enum ContractError {
     #[error(transparent)]
     Version(#[from] cw2::VersionError),
 
+    #[error(transparent)]
+    Verification(#[from] VerificationError),
+
+    #[error(transparent)]
+    Payment(#[from] PaymentError),
+
     #[error("token_id already claimed")]
     Claimed {},
 
@@ -26,10 +33,22 @@ pub enum ContractError {
     #[error("Transfer is not allowed")]
     TransferDisabled {},
 
+    #[error("Transfer is not allowed: the transfer deadline has passed")]
+    TransferDeadlineExpired {},
+
+    #[error("Transfer is not allowed: POAP {token_id} has already been transferred")]
+    AlreadyTransferred { token_id: String },
+
+    #[error("Transfer is not allowed: {recipient} already owns a POAP")]
+    RecipientAlreadyHolder { recipient: String },
+
+    #[error("Transfer is not allowed: {recipient} is not an allowlisted contract")]
+    RecipientNotAllowlisted { recipient: String },
+
     #[error("Mint is not allowed")]
     MintDisabled {},
 
-    #[error("{user} already owns a POAP")]
+    #[error("{user} already owns the maximum number of POAPs allowed per address")]
     PoapAlreadyMinted { user: String },
 
     #[error("You don't have the permission to mint")]
@@ -43,6 +62,130 @@ pub enum ContractError {
 
     #[error("Start time must be smaller than end time")]
     InvalidTimestampValues {},
+
+    #[error("Invalid claim code hash: must be a 32 bytes SHA-256 hash")]
+    InvalidClaimCodeHash {},
+
+    #[error("Invalid or already used claim code")]
+    InvalidClaimCode {},
+
+    #[error("Invalid merkle root: must be a 32 bytes SHA-256 hash")]
+    InvalidMerkleRoot {},
+
+    #[error("Allowlist minting is not enabled")]
+    AllowlistNotSet {},
+
+    #[error("Invalid merkle proof: {user} is not in the allowlist")]
+    InvalidMerkleProof { user: String },
+
+    #[error("Can't mint: only {remaining} POAPs left out of a max supply of {max_supply}")]
+    MaxSupplyExceeded { max_supply: u64, remaining: u64 },
+
+    #[error("Max supply can't be lower than the {supply} POAPs already minted")]
+    InvalidMaxSupply { supply: u64 },
+
+    #[error("Per address limit must be greater than zero")]
+    InvalidPerAddressLimit {},
+
+    #[error("Invalid voucher signer: must be a 33 or 65 bytes secp256k1 public key")]
+    InvalidVoucherSigner {},
+
+    #[error("Voucher minting is not enabled")]
+    VoucherSignerNotSet {},
+
+    #[error("Invalid voucher signature")]
+    InvalidVoucherSignature {},
+
+    #[error("Voucher expired")]
+    VoucherExpired {},
+
+    #[error("Voucher with nonce {nonce} has already been used")]
+    VoucherAlreadyUsed { nonce: u64 },
+
+    #[error("{user} must have a Desmos profile to mint")]
+    ProfileRequired { user: String },
+
+    #[error("{user} must have a verified {application} application link to mint")]
+    AppLinkRequired { user: String, application: String },
+
+    #[error(
+        "{user} must have the {permission} permission inside the subspace {subspace_id} to mint"
+    )]
+    SubspaceMembershipRequired {
+        user: String,
+        subspace_id: u64,
+        permission: String,
+    },
+
+    #[error("{user} must be a member of the user group {group_id} of the subspace {subspace_id} to mint")]
+    UserGroupMembershipRequired {
+        user: String,
+        subspace_id: u64,
+        group_id: u32,
+    },
+
+    #[error("Event {event_id} not found")]
+    EventNotFound { event_id: u64 },
+
+    #[error("Metadata is frozen and can't be updated")]
+    MetadataFrozen {},
+
+    #[error("Airdrop {job_id} not found")]
+    AirdropNotFound { job_id: u64 },
+
+    #[error("Airdrop {job_id} has been cancelled")]
+    AirdropCancelled { job_id: u64 },
+
+    #[error("All the recipients of airdrop {job_id} have already been processed")]
+    AirdropAlreadyProcessed { job_id: u64 },
+
+    #[error("Airdrop {job_id} has been stopped: {error}")]
+    AirdropFailed { job_id: u64, error: String },
+
+    #[error("The number of airdrop recipients to process must be greater than zero")]
+    InvalidAirdropLimit {},
+
+    #[error("Minter {minter} not found")]
+    MinterNotFound { minter: String },
+
+    #[error("Minter {minter} can mint only {remaining} more POAPs")]
+    MinterQuotaExceeded { minter: String, remaining: u64 },
+
+    #[error("Invalid mint price: the amount of each coin must be greater than zero")]
+    InvalidMintPrice {},
+
+    #[error("Can't mint: the mint price of {mint_price} has not been paid")]
+    InsufficientMintPrice { mint_price: String },
+
+    #[error("Can't withdraw more than the collected {collected}")]
+    InsufficientCollectedFees { collected: Coin },
+
+    #[error("Hook {contract} not found")]
+    HookNotFound { contract: String },
+
+    #[error("Extension queries are not supported by this contract")]
+    ExtensionQueryNotSupported {},
+
+    #[error("Contract is paused")]
+    Paused {},
+
+    #[error("You don't have the permission to pause the contract")]
+    PauseUnauthorized {},
+
+    #[error("Invalid announce configuration: the text can't be empty")]
+    InvalidAnnounceText {},
+
+    #[error("Can't refer yourself")]
+    SelfReferral {},
+
+    #[error("Referrer {referrer} doesn't own a POAP")]
+    ReferrerNotHolder { referrer: String },
+
+    #[error("There are no v1 POAPs left to import")]
+    NoV1TokensToImport {},
+
+    #[error("The number of v1 POAPs to import must be greater than zero")]
+    InvalidImportLimit {},
 }
 
 impl From<Cw721BaseContractError> for ContractError {
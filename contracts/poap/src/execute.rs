@@ -1,24 +1,52 @@
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg};
-use crate::state::PoapContract;
+use crate::extension::PoapExtension;
+use crate::msg::{
+    AnnounceConfig, EligibilityRule, EventInfo, ExecuteMsg, InstantiateMsg, MintInfo,
+    MintToResponse, MintToResult, MintToStatus, MintVoucher, PoapHookMsg, RevocationInfo,
+    TransferPolicy, VoucherSignDoc,
+};
+use crate::state::{AirdropJob, MinterInfo, PoapContract};
 use cosmwasm_std::{
-    Addr, Binary, CustomMsg, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Timestamp,
+    to_binary, to_vec, Addr, Api, BankMsg, Binary, BlockInfo, Coin, CosmosMsg, CustomMsg, DepsMut,
+    Empty, Env, MessageInfo, Order, Querier, Reply, Response, StdError, StdResult, Storage, SubMsg,
+    Uint128, Uint64,
 };
 use cw721::Cw721Execute;
 pub use cw721_base::{
     entry::{execute as _execute, query as _query},
     Cw721Contract, Extension, InstantiateMsg as Cw721BaseInstantiateMsg, MinterResponse,
 };
+use cw_utils::{nonpayable, Expiration, NativeBalance, Scheduled};
+use desmos_bindings::profiles::models_app_links::ApplicationLinkState;
+use desmos_bindings::profiles::querier::ProfilesQuerier;
+use desmos_bindings::subspaces::querier::SubspacesQuerier;
+use desmos_bindings::types::PageRequest;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 use std::fmt::Debug;
+use std::ops::Deref;
+
+/// Length in bytes of a SHA-256 hash.
+const SHA256_HASH_LENGTH: usize = 32;
+/// Length in bytes of a compressed secp256k1 public key.
+const SECP256K1_COMPRESSED_PUBKEY_LENGTH: usize = 33;
+/// Length in bytes of an uncompressed secp256k1 public key.
+const SECP256K1_UNCOMPRESSED_PUBKEY_LENGTH: usize = 65;
+/// Number of POAPs that an address can own if not configured otherwise.
+const DEFAULT_PER_ADDRESS_LIMIT: u32 = 1;
+/// Placeholder of the metadata URIs that is replaced with the id of each POAP.
+const TOKEN_ID_PLACEHOLDER: &str = "{token_id}";
+/// Id of the replies of the hook contracts that ignore the errors.
+const HOOK_REPLY_ID: u64 = 1;
 
-impl<'a, T, C, E, Q> PoapContract<'a, T, C, E, Q>
+impl<'a, T, C, E, Q, X> PoapContract<'a, T, C, E, Q, X>
 where
     T: Serialize + DeserializeOwned + Clone + Debug,
     C: CustomMsg,
     E: CustomMsg,
     Q: CustomMsg,
+    X: PoapExtension<T, C, E, Q>,
 {
     pub fn instantiate(
         &self,
@@ -50,16 +78,44 @@ where
             .save(deps.storage, &msg.is_transferable)?;
         self.is_mintable.save(deps.storage, &msg.is_mintable)?;
 
-        if msg.mint_start_time.is_some()
-            && msg.mint_end_time.is_some()
-            && msg.mint_start_time.unwrap() >= msg.mint_end_time.unwrap()
-        {
-            return Err(ContractError::InvalidTimestampValues {});
-        }
-
+        assert_valid_mint_window(&msg.mint_start_time, &msg.mint_end_time)?;
         self.mint_start_time
             .save(deps.storage, &msg.mint_start_time)?;
         self.mint_end_time.save(deps.storage, &msg.mint_end_time)?;
+        self.next_poap_id.save(deps.storage, &1)?;
+
+        if let Some(max_supply) = msg.max_supply {
+            self.max_supply.save(deps.storage, &max_supply)?;
+        }
+
+        let per_address_limit = msg.per_address_limit.unwrap_or(DEFAULT_PER_ADDRESS_LIMIT);
+        if per_address_limit == 0 {
+            return Err(ContractError::InvalidPerAddressLimit {});
+        }
+        self.per_address_limit
+            .save(deps.storage, &per_address_limit)?;
+
+        if let Some(mint_price) = msg.mint_price.filter(|price| !price.is_empty()) {
+            if mint_price.iter().any(|coin| coin.amount.is_zero()) {
+                return Err(ContractError::InvalidMintPrice {});
+            }
+            self.mint_price.save(deps.storage, &mint_price)?;
+        }
+
+        if let Some(transfer_policy) = msg.transfer_policy {
+            let transfer_policy = validate_transfer_policy(deps.api, transfer_policy)?;
+            self.transfer_policy.save(deps.storage, &transfer_policy)?;
+        }
+
+        if let Some(pauser) = msg.pauser {
+            let pauser = deps.api.addr_validate(&pauser)?;
+            self.pauser.save(deps.storage, &pauser)?;
+        }
+
+        if let Some(announce) = msg.announce {
+            assert_valid_announce(&announce)?;
+            self.announce.save(deps.storage, &announce)?;
+        }
 
         Ok(Response::default())
     }
@@ -71,6 +127,17 @@ where
         info: MessageInfo,
         msg: ExecuteMsg<T, E>,
     ) -> Result<Response<C>, ContractError> {
+        // While paused only the ownership and the pause itself can be managed.
+        if !matches!(
+            msg,
+            ExecuteMsg::Pause {}
+                | ExecuteMsg::Unpause {}
+                | ExecuteMsg::UpdatePauser { .. }
+                | ExecuteMsg::UpdateOwnership(_)
+        ) {
+            self.assert_not_paused(deps.storage)?;
+        }
+
         match msg {
             ExecuteMsg::TransferNft {
                 recipient,
@@ -81,52 +148,245 @@ where
                 msg,
                 token_id,
             } => self.send_poap(deps, env, info, contract, token_id, msg),
+            ExecuteMsg::Approve {
+                spender,
+                token_id,
+                expires,
+            } => self.approve_poap(deps, env, info, spender, token_id, expires),
+            ExecuteMsg::ApproveAll { operator, expires } => {
+                self.approve_all_poaps(deps, env, info, operator, expires)
+            }
             ExecuteMsg::UpdateMinter { minter } => self.update_minter(deps, env, info, minter),
             ExecuteMsg::SetMintable { mintable } => self.set_mintable(deps, env, info, mintable),
             ExecuteMsg::SetTransferable { transferable } => {
                 self.set_transferable(deps, env, info, transferable)
             }
+            ExecuteMsg::SetTransferPolicy { policy } => {
+                self.set_transfer_policy(deps, env, info, policy)
+            }
             ExecuteMsg::SetMintStartEndTime {
                 start_time,
                 end_time,
             } => self.set_mint_start_end_time(deps, env, info, start_time, end_time),
-            ExecuteMsg::Mint { extension } => self.mint(deps, env, info, extension),
-            ExecuteMsg::MintTo { extension, users } => {
-                self.mint_to(deps, env, info, users, extension)
+            ExecuteMsg::Mint {
+                event_id,
+                extension,
+                referrer,
+            } => self.mint(deps, env, info, event_id, extension, referrer),
+            ExecuteMsg::MintTo {
+                event_id,
+                extension,
+                users,
+                skip_invalid,
+            } => self.mint_to(
+                deps,
+                env,
+                info,
+                event_id,
+                users,
+                extension,
+                skip_invalid.unwrap_or(false),
+            ),
+            ExecuteMsg::Burn { token_id } => self.burn_poap(deps, env, info, token_id),
+            ExecuteMsg::AddClaimCodes { code_hashes } => {
+                self.add_claim_codes(deps, env, info, code_hashes)
+            }
+            ExecuteMsg::RevokeClaimCodes { code_hashes } => {
+                self.revoke_claim_codes(deps, env, info, code_hashes)
+            }
+            ExecuteMsg::MintWithCode {
+                event_id,
+                code,
+                extension,
+            } => self.mint_with_code(deps, env, info, event_id, code, extension),
+            ExecuteMsg::SetAllowlist { merkle_root } => {
+                self.set_allowlist(deps, env, info, merkle_root)
+            }
+            ExecuteMsg::MintWithProof {
+                event_id,
+                proof,
+                extension,
+            } => self.mint_with_proof(deps, env, info, event_id, proof, extension),
+            ExecuteMsg::UpdateVoucherSigner { public_key } => {
+                self.update_voucher_signer(deps, env, info, public_key)
+            }
+            ExecuteMsg::MintWithVoucher {
+                voucher,
+                signature,
+                extension,
+            } => self.mint_with_voucher(deps, env, info, voucher, signature, extension),
+            ExecuteMsg::SetEligibilityRules { rules } => {
+                self.set_eligibility_rules(deps, env, info, rules)
+            }
+            ExecuteMsg::SetMaxSupply { max_supply } => {
+                self.set_max_supply(deps, env, info, max_supply)
+            }
+            ExecuteMsg::SetPerAddressLimit { limit } => {
+                self.set_per_address_limit(deps, env, info, limit)
+            }
+            ExecuteMsg::CreateEvent {
+                metadata_uri,
+                minter,
+                is_transferable,
+                is_mintable,
+                mint_start_time,
+                mint_end_time,
+            } => self.create_event(
+                deps,
+                env,
+                info,
+                metadata_uri,
+                minter,
+                is_transferable,
+                is_mintable,
+                mint_start_time,
+                mint_end_time,
+            ),
+            ExecuteMsg::UpdateEvent {
+                event_id,
+                metadata_uri,
+                minter,
+                is_transferable,
+                is_mintable,
+                mint_start_time,
+                mint_end_time,
+            } => self.update_event(
+                deps,
+                env,
+                info,
+                event_id,
+                metadata_uri,
+                minter,
+                is_transferable,
+                is_mintable,
+                mint_start_time,
+                mint_end_time,
+            ),
+            ExecuteMsg::UpdateMetadataUri {
+                metadata_uri,
+                refresh_token_ids,
+            } => self.update_metadata_uri(deps, env, info, metadata_uri, refresh_token_ids),
+            ExecuteMsg::FreezeMetadata {} => self.freeze_metadata(deps, env, info),
+            ExecuteMsg::CreateAirdrop {
+                event_id,
+                recipients,
+                extension,
+            } => self.create_airdrop(deps, env, info, event_id, recipients, extension),
+            ExecuteMsg::AddAirdropRecipients { job_id, recipients } => {
+                self.add_airdrop_recipients(deps, env, info, job_id, recipients)
+            }
+            ExecuteMsg::ProcessAirdrop { job_id, limit } => {
+                self.process_airdrop(deps, env, info, job_id, limit)
+            }
+            ExecuteMsg::CancelAirdrop { job_id } => self.cancel_airdrop(deps, env, info, job_id),
+            ExecuteMsg::Recover {
+                token_id,
+                new_owner,
+            } => self.recover(deps, env, info, token_id, new_owner),
+            ExecuteMsg::RevokePoap { token_id, reason } => {
+                self.revoke_poap(deps, env, info, token_id, reason)
+            }
+            ExecuteMsg::AddMinter {
+                minter,
+                quota,
+                expiration,
+            } => self.add_minter(deps, env, info, minter, quota, expiration),
+            ExecuteMsg::RemoveMinter { minter } => self.remove_minter(deps, env, info, minter),
+            ExecuteMsg::SetMintPrice { mint_price } => {
+                self.set_mint_price(deps, env, info, mint_price)
             }
+            ExecuteMsg::WithdrawFees { receiver, amount } => {
+                self.withdraw_fees(deps, env, info, receiver, amount)
+            }
+            ExecuteMsg::AddHook {
+                contract,
+                ignore_errors,
+            } => self.add_hook(deps, env, info, contract, ignore_errors),
+            ExecuteMsg::RemoveHook { contract } => self.remove_hook(deps, env, info, contract),
+            ExecuteMsg::Pause {} => self.pause(deps, env, info),
+            ExecuteMsg::Unpause {} => self.unpause(deps, env, info),
+            ExecuteMsg::UpdatePauser { pauser } => self.update_pauser(deps, env, info, pauser),
+            ExecuteMsg::SetAnnounce { announce } => self.set_announce(deps, env, info, announce),
+            ExecuteMsg::ImportV1Tokens { limit } => self.import_v1_tokens(deps, env, info, limit),
+            ExecuteMsg::Extension { msg } => self.extension.execute(deps, env, info, msg),
             _ => self
                 .cw721_base
                 .execute(deps, env, info, msg.into())
                 .map_err(|e| e.into()),
         }
     }
+
+    pub fn reply(
+        &self,
+        _deps: DepsMut,
+        _env: Env,
+        msg: Reply,
+    ) -> Result<Response<C>, ContractError> {
+        match msg.id {
+            // A hook that ignores the errors has failed, the action that
+            // triggered it is not reverted.
+            HOOK_REPLY_ID => Ok(Response::new()
+                .add_attribute("action", "poap_hook_failed")
+                .add_attribute("error", msg.result.unwrap_err())),
+            id => Err(StdError::generic_err(format!("Unknown reply id: {id}")).into()),
+        }
+    }
 }
 
-impl<'a, T, C, E, Q> PoapContract<'a, T, C, E, Q>
+impl<'a, T, C, E, Q, X> PoapContract<'a, T, C, E, Q, X>
 where
     T: Serialize + DeserializeOwned + Clone + Debug,
     C: CustomMsg,
     E: CustomMsg,
     Q: CustomMsg,
+    X: PoapExtension<T, C, E, Q>,
 {
     /// Transfer a POAP to another user.
     /// * `recipient` - Address of the user that will receive the POAP.
     /// * `token_id` - Id of the POAP to transfer.
     pub fn transfer_poap(
         &self,
-        deps: DepsMut,
+        mut deps: DepsMut,
         env: Env,
         info: MessageInfo,
         recipient: String,
         token_id: String,
     ) -> Result<Response<C>, ContractError> {
         // Check if the transfer is allowed.
-        self.assert_is_transferable(deps.storage)?;
+        self.assert_is_transferable(deps.storage, &token_id)?;
+        let recipient_addr = deps.api.addr_validate(&recipient)?;
+        self.assert_transfer_policy(
+            deps.storage,
+            &env.block,
+            Some(&token_id),
+            &recipient_addr,
+            false,
+        )?;
 
-        return self
-            .cw721_base
-            .transfer_nft(deps, env, info, recipient, token_id)
-            .map_err(|e| e.into());
+        let previous_owner = self.cw721_base.tokens.load(deps.storage, &token_id)?.owner;
+        let height = env.block.height;
+        let response = self.cw721_base.transfer_nft(
+            deps.branch(),
+            env,
+            info,
+            recipient.clone(),
+            token_id.clone(),
+        )?;
+        let owner = self.cw721_base.tokens.load(deps.storage, &token_id)?.owner;
+        self.update_holder_index(deps.storage, height, Some(&previous_owner), Some(&owner))?;
+        self.transferred_tokens
+            .save(deps.storage, &token_id, &Empty {})?;
+        let hooks = self.hook_msgs(
+            deps.storage,
+            PoapHookMsg::Transferred {
+                event_id: self.token_events.may_load(deps.storage, &token_id)?,
+                token_id,
+                previous_owner: previous_owner.to_string(),
+                owner: recipient,
+            },
+        )?;
+
+        Ok(response.add_submessages(hooks))
     }
 
     /// Send a POAP to a contract and trigger an action on the contract.
@@ -135,7 +395,7 @@ where
     /// * `msg` - Message that the recipient contract will execute.
     pub fn send_poap(
         &self,
-        deps: DepsMut,
+        mut deps: DepsMut,
         env: Env,
         info: MessageInfo,
         contract: String,
@@ -143,12 +403,116 @@ where
         msg: Binary,
     ) -> Result<Response<C>, ContractError> {
         // Check if the transfer is allowed.
-        self.assert_is_transferable(deps.storage)?;
+        self.assert_is_transferable(deps.storage, &token_id)?;
+        let contract_addr = deps.api.addr_validate(&contract)?;
+        self.assert_transfer_policy(
+            deps.storage,
+            &env.block,
+            Some(&token_id),
+            &contract_addr,
+            false,
+        )?;
+
+        let previous_owner = self.cw721_base.tokens.load(deps.storage, &token_id)?.owner;
+        let height = env.block.height;
+        let response = self.cw721_base.send_nft(
+            deps.branch(),
+            env,
+            info,
+            contract.clone(),
+            token_id.clone(),
+            msg,
+        )?;
+        let owner = self.cw721_base.tokens.load(deps.storage, &token_id)?.owner;
+        self.update_holder_index(deps.storage, height, Some(&previous_owner), Some(&owner))?;
+        self.transferred_tokens
+            .save(deps.storage, &token_id, &Empty {})?;
+        let hooks = self.hook_msgs(
+            deps.storage,
+            PoapHookMsg::Transferred {
+                event_id: self.token_events.may_load(deps.storage, &token_id)?,
+                token_id,
+                previous_owner: previous_owner.to_string(),
+                owner: contract,
+            },
+        )?;
+
+        Ok(response.add_submessages(hooks))
+    }
+
+    /// Allows a user to transfer or send a POAP on behalf of its owner.
+    /// * `spender` - Address of the approved user.
+    /// * `token_id` - Id of the POAP.
+    /// * `expires` - When the approval expires, if None it never expires.
+    pub fn approve_poap(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response<C>, ContractError> {
+        let spender_addr = deps.api.addr_validate(&spender)?;
+        self.assert_transfer_policy(
+            deps.storage,
+            &env.block,
+            Some(&token_id),
+            &spender_addr,
+            true,
+        )?;
+
+        Ok(self
+            .cw721_base
+            .approve(deps, env, info, spender, token_id, expires)?)
+    }
+
+    /// Allows an operator to transfer or send all the POAPs of the sender.
+    /// * `operator` - Address of the approved operator.
+    /// * `expires` - When the approval expires, if None it never expires.
+    pub fn approve_all_poaps(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        operator: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response<C>, ContractError> {
+        let operator_addr = deps.api.addr_validate(&operator)?;
+        self.assert_transfer_policy(deps.storage, &env.block, None, &operator_addr, true)?;
+
+        Ok(self
+            .cw721_base
+            .approve_all(deps, env, info, operator, expires)?)
+    }
 
-        return self
+    /// Burn a POAP that the sender owns or has been approved to.
+    /// * `token_id` - Id of the POAP to burn.
+    pub fn burn_poap(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let owner = self.cw721_base.tokens.load(deps.storage, &token_id)?.owner;
+        let event_id = self.token_events.may_load(deps.storage, &token_id)?;
+        let height = env.block.height;
+        let response = self
             .cw721_base
-            .send_nft(deps, env, info, contract, token_id, msg)
-            .map_err(|e| e.into());
+            .burn(deps.branch(), env, info, token_id.clone())?;
+        self.remove_poap_records(deps.storage, &token_id)?;
+        self.update_holder_index(deps.storage, height, Some(&owner), None)?;
+        let hooks = self.hook_msgs(
+            deps.storage,
+            PoapHookMsg::Burned {
+                token_id,
+                owner: owner.to_string(),
+                event_id,
+            },
+        )?;
+
+        Ok(response.add_submessages(hooks))
     }
 
     /// Updates who have the minting permissions, this action can be executed only from
@@ -174,6 +538,78 @@ where
             ))
     }
 
+    /// Registers an additional minter or updates the configuration of an already registered one,
+    /// this action can be executed only from the contract admin.
+    /// The registered minters can mint the POAPs of every event on behalf of other users.
+    /// * `minter` - Address of the minter.
+    /// * `quota` - Maximum number of POAPs that the minter can mint, if None there is no limit.
+    /// * `expiration` - When the minter loses the minting permissions, if None they never expire.
+    pub fn add_minter(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        minter: String,
+        quota: Option<u64>,
+        expiration: Option<Expiration>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+        let minter = deps.api.addr_validate(&minter)?;
+
+        // Keep the number of POAPs already minted when updating a minter.
+        let minted = self
+            .minters
+            .may_load(deps.storage, &minter)?
+            .map_or(0, |info| info.minted);
+        self.minters.save(
+            deps.storage,
+            &minter,
+            &MinterInfo {
+                quota,
+                minted,
+                expiration,
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "add_minter")
+            .add_attribute("sender", info.sender)
+            .add_attribute("minter", minter)
+            .add_attribute(
+                "quota",
+                quota.map_or_else(|| "none".to_string(), |q| q.to_string()),
+            )
+            .add_attribute(
+                "expiration",
+                expiration.map_or_else(|| "none".to_string(), |e| e.to_string()),
+            ))
+    }
+
+    /// Removes a minter registered with `AddMinter`, this action can be executed only from
+    /// the contract admin.
+    /// * `minter` - Address of the minter.
+    pub fn remove_minter(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        minter: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+        let minter = deps.api.addr_validate(&minter)?;
+        if !self.minters.has(deps.storage, &minter) {
+            return Err(ContractError::MinterNotFound {
+                minter: minter.to_string(),
+            });
+        }
+        self.minters.remove(deps.storage, &minter);
+
+        Ok(Response::new()
+            .add_attribute("action", "remove_minter")
+            .add_attribute("sender", info.sender)
+            .add_attribute("minter", minter))
+    }
+
     /// Updates the POAP mintability, this action can be executed only from
     /// the contract admin.
     /// * `mintable` - true if the POAP can be minted from the users, false otherwise.
@@ -212,27 +648,57 @@ where
             .add_attribute("transferable", transferable.to_string()))
     }
 
+    /// Sets the policy that restricts the transfers of the POAPs, this action can be executed
+    /// only from the contract admin.
+    /// * `policy` - The new transfer policy, if None the transferable POAPs can be
+    ///   freely transferred.
+    pub fn set_transfer_policy(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        policy: Option<TransferPolicy>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+
+        match policy {
+            Some(policy) => {
+                let policy = validate_transfer_policy(deps.api, policy)?;
+                self.transfer_policy.save(deps.storage, &policy)?;
+            }
+            None => self.transfer_policy.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_transfer_policy")
+            .add_attribute("sender", info.sender)
+            .add_attribute(
+                "transfer_policy",
+                self.transfer_policy
+                    .may_load(deps.storage)?
+                    .map_or_else(|| "none".to_string(), |policy| policy.to_string()),
+            ))
+    }
+
     /// Sets the time period on which the minting is allowed, this action can be executed only from
     /// the contract admin.
-    /// * `start_time` - Timestamp at which the minting of the POAP will be enabled.
+    /// * `start_time` - Block height or timestamp at which the minting of the POAP will be enabled.
     /// If None, the minting is always enabled.
-    /// * `end_time` - Timestamp at which the minting of the POAP will be disabled.    
+    /// * `end_time` - Block height or timestamp at which the minting of the POAP will be disabled.
     /// If not set, the minting will never end.
     pub fn set_mint_start_end_time(
         &self,
         deps: DepsMut,
         _env: Env,
         info: MessageInfo,
-        start_time: Option<Timestamp>,
-        end_time: Option<Timestamp>,
+        start_time: Option<Scheduled>,
+        end_time: Option<Expiration>,
     ) -> Result<Response<C>, ContractError> {
         self.assert_is_admin(deps.storage, &info.sender)?;
 
         // Ensure that if we have both start time and end time the start time is lower then
         // the end time.
-        if start_time.is_some() && end_time.is_some() && start_time.unwrap() >= end_time.unwrap() {
-            return Err(ContractError::InvalidTimestampValues {});
-        }
+        assert_valid_mint_window(&start_time, &end_time)?;
 
         // Update the start and end time
         self.mint_start_time.save(deps.storage, &start_time)?;
@@ -252,158 +718,1899 @@ where
     }
 
     /// Mint a POAP to the user that is calling this action.
+    /// * `event_id` - Id of the event for which the POAP will be minted,
+    ///   if None the POAP is minted for the contract default event.
+    /// * `referrer` - Optional address of the user that referred the caller.
     pub fn mint(
         &self,
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
+        event_id: Option<u64>,
         extension: T,
+        referrer: Option<String>,
     ) -> Result<Response<C>, ContractError> {
-        self.assert_user_can_mint(deps.storage, &info.sender, &env)?;
-        let token_id = self.mint_to_user(deps.storage, &info.sender, extension)?;
+        let event = self.event_info(deps.storage, event_id)?;
+        self.assert_user_can_mint(
+            deps.storage,
+            deps.querier.deref(),
+            &info.sender,
+            &env,
+            &event,
+        )?;
+        let referrer = referrer
+            .map(|referrer| deps.api.addr_validate(&referrer))
+            .transpose()?;
+        if let Some(referrer) = &referrer {
+            self.assert_valid_referrer(deps.storage, &info.sender, referrer)?;
+        }
 
-        Ok(Response::new()
+        // The minters and the admin don't pay the mint price.
+        let mint_price = self.mint_price.may_load(deps.storage)?.unwrap_or_default();
+        let is_exempt = self
+            .assert_is_event_minter_or_admin(deps.storage, &env.block, &info.sender, event_id)
+            .is_ok();
+        let refund = if mint_price.is_empty() || is_exempt {
+            nonpayable(&info)?;
+            vec![]
+        } else {
+            let refund = mint_price_refund(&mint_price, &info.funds)?;
+            for coin in &mint_price {
+                self.collected_fees.update(
+                    deps.storage,
+                    &coin.denom,
+                    |collected| -> StdResult<_> { Ok(collected.unwrap_or_default() + coin.amount) },
+                )?;
+            }
+            refund
+        };
+
+        let token_id = self.mint_to_user(
+            deps.storage,
+            &env.block,
+            &info.sender,
+            &info.sender,
+            event_id,
+            extension,
+        )?;
+        if let Some(referrer) = &referrer {
+            self.record_referral(deps.storage, referrer)?;
+        }
+
+        let hooks = self.hook_msgs(
+            deps.storage,
+            PoapHookMsg::Minted {
+                token_id: token_id.clone(),
+                owner: info.sender.to_string(),
+                event_id,
+            },
+        )?;
+        let announcements =
+            self.announce_msgs(deps.storage, &env, &[(info.sender.as_str(), &token_id)])?;
+
+        let mut response = Response::new()
+            .add_submessages(hooks)
+            .add_messages(announcements)
             .add_attribute("action", "mint")
-            .add_attribute("owner", info.sender)
-            .add_attribute("token_id", token_id))
+            .add_attribute("owner", &info.sender)
+            .add_attribute(
+                "event_id",
+                event_id.map_or_else(|| "none".to_string(), |id| id.to_string()),
+            )
+            .add_attribute("token_id", token_id)
+            .add_attribute(
+                "referrer",
+                referrer.map_or_else(|| "none".to_string(), |referrer| referrer.to_string()),
+            );
+        if !refund.is_empty() {
+            response = response.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: refund,
+            });
+        }
+
+        Ok(response)
     }
 
-    /// Mint a POAP to a list of user, this action can be executed only from the contract minter.
+    /// Mint a POAP to a list of user, this action can be executed only from the event minter.
+    /// * `event_id` - Id of the event for which the POAPs will be minted,
+    ///   if None the POAPs are minted for the contract default event.
     /// * `users` - List of users for whom the POAP will be minted.
+    /// * `skip_invalid` - If true, the users that have an invalid address or already own
+    ///   the POAP are skipped instead of failing the whole batch.
+    #[allow(clippy::too_many_arguments)]
     pub fn mint_to(
         &self,
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
+        event_id: Option<u64>,
         users: Vec<String>,
         extension: T,
+        skip_invalid: bool,
     ) -> Result<Response<C>, ContractError> {
-        // Check if the sender is the admin or the minter.
-        let can_mint = self.assert_is_minter(deps.storage, &info.sender).is_ok()
-            || self.assert_is_admin(deps.storage, &info.sender).is_ok();
+        nonpayable(&info)?;
 
-        if !can_mint {
-            return Err(ContractError::MintUnauthorized {});
+        // Check if the sender is the admin or the event minter.
+        self.assert_is_event_minter_or_admin(deps.storage, &env.block, &info.sender, event_id)?;
+
+        // Ensure that the whole batch can be minted before minting any POAP.
+        // When skipping the invalid users the batch size is not known in advance,
+        // so the max supply is checked for each POAP.
+        if !skip_invalid {
+            self.assert_max_supply(deps.storage, users.len() as u64)?;
         }
 
-        let mut minted_tokens = Vec::<String>::with_capacity(users.len());
+        let mut results = Vec::<MintToResult>::with_capacity(users.len());
         for user in users {
-            let user_addr = deps.api.addr_validate(&user)?;
-            minted_tokens.push(self.mint_to_user(deps.storage, &user_addr, extension.clone())?);
+            let status = self.mint_to_address(
+                deps.storage,
+                deps.api,
+                &env.block,
+                &info.sender,
+                &user,
+                event_id,
+                extension.clone(),
+                skip_invalid,
+            )?;
+            results.push(MintToResult { user, status });
         }
 
-        Ok(Response::new()
+        let mut minted = Vec::<(&str, &str)>::new();
+        let mut already_owned = Vec::<&str>::new();
+        let mut invalid_addresses = Vec::<&str>::new();
+        for result in &results {
+            match &result.status {
+                MintToStatus::Minted { token_id } => minted.push((&result.user, token_id)),
+                MintToStatus::AlreadyOwned {} => already_owned.push(&result.user),
+                MintToStatus::InvalidAddress {} => invalid_addresses.push(&result.user),
+            }
+        }
+        self.consume_minter_quota(deps.storage, &info.sender, event_id, minted.len() as u64)?;
+
+        let hooks = self.minted_hook_msgs(deps.storage, event_id, &results)?;
+        let announcements = self.announce_msgs(deps.storage, &env, &minted)?;
+        let minted_tokens: Vec<&str> = minted.iter().map(|(_, token_id)| *token_id).collect();
+
+        let mut response = Response::new()
+            .add_submessages(hooks)
+            .add_messages(announcements)
             .add_attribute("action", "mint_to")
             .add_attribute("minter", info.sender)
-            .add_attribute("token_ids", minted_tokens.join(", ")))
-    }
-}
+            .add_attribute(
+                "event_id",
+                event_id.map_or_else(|| "none".to_string(), |id| id.to_string()),
+            )
+            .add_attribute("token_ids", minted_tokens.join(", "));
+        if skip_invalid {
+            response = response
+                .add_attribute("already_owned", already_owned.join(", "))
+                .add_attribute("invalid_addresses", invalid_addresses.join(", "));
+        }
 
-// Utility functions
-impl<'a, T, C, E, Q> PoapContract<'a, T, C, E, Q>
-where
-    T: Serialize + DeserializeOwned + Clone + Debug,
-    C: CustomMsg,
-    E: CustomMsg,
-    Q: CustomMsg,
-{
-    /// Computes the id of the next POAP to mint.
-    pub fn generate_poap_id(&self, storage: &dyn Storage) -> StdResult<String> {
-        Ok(format!("{}", 1 + self.cw721_base.token_count(storage)?))
+        Ok(response.set_data(to_binary(&MintToResponse { results })?))
     }
 
-    /// Mint a POAP to an user.
-    /// * `owner` - User for whom the POAP will be minted.
-    pub fn mint_to_user(
+    /// Registers a batch of claim codes, this action can be executed only from
+    /// the contract admin.
+    /// * `code_hashes` - SHA-256 hashes of the claim codes to register.
+    pub fn add_claim_codes(
         &self,
-        storage: &mut dyn Storage,
-        owner: &Addr,
-        extension: T,
-    ) -> Result<String, ContractError> {
-        // Check if this user have already minted a poap.
-        self.assert_user_dont_own_a_poap(storage, &owner)?;
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        code_hashes: Vec<Binary>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
 
-        // Create the token
-        let token = cw721_base::state::TokenInfo {
-            owner: owner.clone(),
-            approvals: vec![],
-            token_uri: Some(self.metadata_uri.load(storage)?),
-            extension,
-        };
+        let mut added: u64 = 0;
+        for code_hash in code_hashes {
+            if code_hash.len() != SHA256_HASH_LENGTH {
+                return Err(ContractError::InvalidClaimCodeHash {});
+            }
 
-        // Generate the token id
-        let token_id = self.generate_poap_id(storage)?;
-        self.cw721_base
-            .tokens
-            .update(storage, &token_id, |old| match old {
-                Some(_) => Err(ContractError::Claimed {}),
-                None => Ok(token),
-            })?;
+            // Skip the codes that are already registered to keep the count consistent.
+            if !self.claim_codes.has(deps.storage, code_hash.as_slice()) {
+                self.claim_codes
+                    .save(deps.storage, code_hash.as_slice(), &Empty {})?;
+                added += 1;
+            }
+        }
 
-        self.cw721_base.increment_tokens(storage)?;
+        let count = self.claim_codes_count(deps.storage)? + added;
+        self.claim_codes_count.save(deps.storage, &count)?;
 
-        Ok(token_id)
+        Ok(Response::new()
+            .add_attribute("action", "add_claim_codes")
+            .add_attribute("sender", info.sender)
+            .add_attribute("added", added.to_string()))
     }
 
-    /// Asserts that the provided address is the contract admin.
-    /// * `sender` - Address that will be checked.
-    pub fn assert_is_admin(
+    /// Removes a batch of unused claim codes, this action can be executed only from
+    /// the contract admin.
+    /// * `code_hashes` - SHA-256 hashes of the claim codes to remove.
+    pub fn revoke_claim_codes(
         &self,
-        storage: &dyn Storage,
-        sender: &Addr,
-    ) -> Result<(), ContractError> {
-        cw_ownable::assert_owner(storage, sender).map_err(|e| ContractError::Ownership(e))
-    }
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        code_hashes: Vec<Binary>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
 
-    /// Asserts that the provided address is the contract minter.
-    /// * `sender` - Address that will be checked.
-    pub fn assert_is_minter(
-        &self,
-        storage: &dyn Storage,
-        sender: &Addr,
-    ) -> Result<(), ContractError> {
-        let minter = self.minter.load(storage)?;
-        if minter.is_none() || minter.unwrap().ne(sender) {
-            return Err(ContractError::MintUnauthorized {});
+        let mut revoked: u64 = 0;
+        for code_hash in code_hashes {
+            // Ignore the codes that are unknown or have already been used.
+            if self.claim_codes.has(deps.storage, code_hash.as_slice()) {
+                self.claim_codes.remove(deps.storage, code_hash.as_slice());
+                revoked += 1;
+            }
         }
 
-        Ok(())
+        let count = self.claim_codes_count(deps.storage)? - revoked;
+        self.claim_codes_count.save(deps.storage, &count)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "revoke_claim_codes")
+            .add_attribute("sender", info.sender)
+            .add_attribute("revoked", revoked.to_string()))
     }
 
-    /// Check whether a user owns a POAP.
-    /// * `user` - Address that will be checked.
-    pub fn assert_user_dont_own_a_poap(
+    /// Mint a POAP to the user that is calling this action using a claim code.
+    /// Since the claim codes are handed out by the event organizers, the user
+    /// doesn't pay the mint price.
+    /// * `event_id` - Id of the event for which the POAP will be minted,
+    ///   if None the POAP is minted for the contract default event.
+    /// * `code` - The claim code, its SHA-256 hash must have been registered with `AddClaimCodes`.
+    pub fn mint_with_code(
         &self,
-        storage: &dyn Storage,
-        user: &Addr,
-    ) -> Result<(), ContractError> {
-        // Check if this user have already minted a poap.
-        if !self
-            .cw721_base
-            .tokens
-            .idx
-            .owner
-            .prefix(user.clone())
-            .is_empty(storage)
-        {
-            return Err(ContractError::PoapAlreadyMinted {
-                user: user.to_string(),
-            });
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        event_id: Option<u64>,
+        code: String,
+        extension: T,
+    ) -> Result<Response<C>, ContractError> {
+        nonpayable(&info)?;
+
+        let event = self.event_info(deps.storage, event_id)?;
+        self.assert_user_can_mint(
+            deps.storage,
+            deps.querier.deref(),
+            &info.sender,
+            &env,
+            &event,
+        )?;
+
+        // Consume the claim code so that it can't be used again.
+        let code_hash = Sha256::digest(code.as_bytes());
+        if !self.claim_codes.has(deps.storage, code_hash.as_slice()) {
+            return Err(ContractError::InvalidClaimCode {});
         }
+        self.claim_codes.remove(deps.storage, code_hash.as_slice());
+        let count = self.claim_codes_count(deps.storage)? - 1;
+        self.claim_codes_count.save(deps.storage, &count)?;
+
+        let token_id = self.mint_to_user(
+            deps.storage,
+            &env.block,
+            &info.sender,
+            &info.sender,
+            event_id,
+            extension,
+        )?;
 
-        return Ok(());
+        let hooks = self.hook_msgs(
+            deps.storage,
+            PoapHookMsg::Minted {
+                token_id: token_id.clone(),
+                owner: info.sender.to_string(),
+                event_id,
+            },
+        )?;
+        let announcements =
+            self.announce_msgs(deps.storage, &env, &[(info.sender.as_str(), &token_id)])?;
+
+        Ok(Response::new()
+            .add_submessages(hooks)
+            .add_messages(announcements)
+            .add_attribute("action", "mint_with_code")
+            .add_attribute("owner", info.sender)
+            .add_attribute(
+                "event_id",
+                event_id.map_or_else(|| "none".to_string(), |id| id.to_string()),
+            )
+            .add_attribute("token_id", token_id))
     }
 
-    /// Asserts if an user can mint a POAP.
-    /// * `user` - Address that will be checked.
-    pub fn assert_user_can_mint(
+    /// Sets the Merkle root of the allowlist, this action can be executed only from
+    /// the contract admin.
+    /// * `merkle_root` - SHA-256 Merkle root of the allowed addresses,
+    ///   if None the allowlist minting is disabled.
+    pub fn set_allowlist(
         &self,
-        storage: &dyn Storage,
-        user: &Addr,
-        env: &Env,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        merkle_root: Option<Binary>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+
+        match &merkle_root {
+            Some(root) => {
+                if root.len() != SHA256_HASH_LENGTH {
+                    return Err(ContractError::InvalidMerkleRoot {});
+                }
+                self.allowlist_root.save(deps.storage, root)?;
+            }
+            None => self.allowlist_root.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_allowlist")
+            .add_attribute("sender", info.sender)
+            .add_attribute(
+                "merkle_root",
+                merkle_root.map_or_else(|| "none".to_string(), |root| root.to_base64()),
+            ))
+    }
+
+    /// Mint a POAP to the user that is calling this action if its address is part
+    /// of the allowlist.
+    /// Since the allowlist is defined by the contract admin, the user doesn't pay
+    /// the mint price.
+    /// * `event_id` - Id of the event for which the POAP will be minted,
+    ///   if None the POAP is minted for the contract default event.
+    /// * `proof` - Merkle proof of the sender address.
+    pub fn mint_with_proof(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        event_id: Option<u64>,
+        proof: Vec<Binary>,
+        extension: T,
+    ) -> Result<Response<C>, ContractError> {
+        nonpayable(&info)?;
+
+        let root = self
+            .allowlist_root
+            .may_load(deps.storage)?
+            .ok_or(ContractError::AllowlistNotSet {})?;
+
+        if !verify_merkle_proof(&root, info.sender.as_bytes(), &proof) {
+            return Err(ContractError::InvalidMerkleProof {
+                user: info.sender.to_string(),
+            });
+        }
+
+        let event = self.event_info(deps.storage, event_id)?;
+        self.assert_user_can_mint(
+            deps.storage,
+            deps.querier.deref(),
+            &info.sender,
+            &env,
+            &event,
+        )?;
+        let token_id = self.mint_to_user(
+            deps.storage,
+            &env.block,
+            &info.sender,
+            &info.sender,
+            event_id,
+            extension,
+        )?;
+
+        let hooks = self.hook_msgs(
+            deps.storage,
+            PoapHookMsg::Minted {
+                token_id: token_id.clone(),
+                owner: info.sender.to_string(),
+                event_id,
+            },
+        )?;
+        let announcements =
+            self.announce_msgs(deps.storage, &env, &[(info.sender.as_str(), &token_id)])?;
+
+        Ok(Response::new()
+            .add_submessages(hooks)
+            .add_messages(announcements)
+            .add_attribute("action", "mint_with_proof")
+            .add_attribute("owner", info.sender)
+            .add_attribute(
+                "event_id",
+                event_id.map_or_else(|| "none".to_string(), |id| id.to_string()),
+            )
+            .add_attribute("token_id", token_id))
+    }
+
+    /// Updates the public key that signs the mint vouchers, this action can be executed only from
+    /// the contract admin.
+    /// * `public_key` - Compressed or uncompressed secp256k1 public key,
+    ///   if None the voucher minting is disabled.
+    pub fn update_voucher_signer(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        public_key: Option<Binary>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+
+        match &public_key {
+            Some(key) => {
+                if key.len() != SECP256K1_COMPRESSED_PUBKEY_LENGTH
+                    && key.len() != SECP256K1_UNCOMPRESSED_PUBKEY_LENGTH
+                {
+                    return Err(ContractError::InvalidVoucherSigner {});
+                }
+                self.voucher_signer.save(deps.storage, key)?;
+            }
+            None => self.voucher_signer.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "update_voucher_signer")
+            .add_attribute("sender", info.sender)
+            .add_attribute(
+                "public_key",
+                public_key.map_or_else(|| "none".to_string(), |key| key.to_base64()),
+            ))
+    }
+
+    /// Mint a POAP to the recipient of a voucher signed by the voucher signer.
+    /// The recipient must satisfy the POAP minting conditions, but since the vouchers
+    /// are issued on behalf of the minter the mint price is not paid.
+    /// * `voucher` - The voucher to redeem.
+    /// * `signature` - Signature of the voucher [`VoucherSignDoc`].
+    pub fn mint_with_voucher(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        voucher: MintVoucher,
+        signature: Binary,
+        extension: T,
+    ) -> Result<Response<C>, ContractError> {
+        nonpayable(&info)?;
+
+        let public_key = self
+            .voucher_signer
+            .may_load(deps.storage)?
+            .ok_or(ContractError::VoucherSignerNotSet {})?;
+
+        if env.block.time >= voucher.expires_at {
+            return Err(ContractError::VoucherExpired {});
+        }
+
+        if self.spent_voucher_nonces.has(deps.storage, voucher.nonce) {
+            return Err(ContractError::VoucherAlreadyUsed {
+                nonce: voucher.nonce,
+            });
+        }
+
+        // Check that the voucher has been signed for this contract.
+        let sign_doc = VoucherSignDoc {
+            contract_address: env.contract.address.to_string(),
+            recipient: voucher.recipient.clone(),
+            event_id: voucher.event_id,
+            expires_at: voucher.expires_at,
+            nonce: voucher.nonce,
+        };
+        let message_hash = Sha256::digest(to_vec(&sign_doc)?);
+        if !deps
+            .api
+            .secp256k1_verify(&message_hash, &signature, &public_key)?
+        {
+            return Err(ContractError::InvalidVoucherSignature {});
+        }
+
+        let recipient = deps.api.addr_validate(&voucher.recipient)?;
+        let event_id = voucher.event_id;
+        let event = self.event_info(deps.storage, event_id)?;
+        self.assert_user_can_mint(deps.storage, deps.querier.deref(), &recipient, &env, &event)?;
+
+        self.spent_voucher_nonces
+            .save(deps.storage, voucher.nonce, &Empty {})?;
+
+        let token_id = self.mint_to_user(
+            deps.storage,
+            &env.block,
+            &info.sender,
+            &recipient,
+            event_id,
+            extension,
+        )?;
+
+        let hooks = self.hook_msgs(
+            deps.storage,
+            PoapHookMsg::Minted {
+                token_id: token_id.clone(),
+                owner: recipient.to_string(),
+                event_id,
+            },
+        )?;
+        let announcements =
+            self.announce_msgs(deps.storage, &env, &[(recipient.as_str(), &token_id)])?;
+
+        Ok(Response::new()
+            .add_submessages(hooks)
+            .add_messages(announcements)
+            .add_attribute("action", "mint_with_voucher")
+            .add_attribute("sender", info.sender)
+            .add_attribute("owner", recipient)
+            .add_attribute(
+                "event_id",
+                event_id.map_or_else(|| "none".to_string(), |id| id.to_string()),
+            )
+            .add_attribute("nonce", voucher.nonce.to_string())
+            .add_attribute("token_id", token_id))
+    }
+
+    /// Sets the rules that the users must satisfy to mint a POAP, this action can be executed
+    /// only from the contract admin.
+    /// * `rules` - The new eligibility rules, if empty every user can mint.
+    pub fn set_eligibility_rules(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        rules: Vec<EligibilityRule>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+        self.eligibility_rules.save(deps.storage, &rules)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_eligibility_rules")
+            .add_attribute("sender", info.sender)
+            .add_attribute("rules_count", rules.len().to_string()))
+    }
+
+    /// Sets the maximum number of POAPs that can be minted, this action can be
+    /// executed only from the contract admin.
+    /// * `max_supply` - The new max supply, if None there is no limit.
+    pub fn set_max_supply(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        max_supply: Option<u64>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+
+        match max_supply {
+            Some(max_supply) => {
+                let supply = self.issued_poaps(deps.storage)?;
+                if max_supply < supply {
+                    return Err(ContractError::InvalidMaxSupply { supply });
+                }
+                self.max_supply.save(deps.storage, &max_supply)?;
+            }
+            None => self.max_supply.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_max_supply")
+            .add_attribute("sender", info.sender)
+            .add_attribute(
+                "max_supply",
+                max_supply.map_or_else(|| "none".to_string(), |s| s.to_string()),
+            ))
+    }
+
+    /// Sets the price that the users pay to mint a POAP with the `Mint` message, this action
+    /// can be executed only from the contract admin.
+    /// * `mint_price` - The new mint price, if empty the minting is free.
+    pub fn set_mint_price(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        mint_price: Vec<Coin>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+        if mint_price.iter().any(|coin| coin.amount.is_zero()) {
+            return Err(ContractError::InvalidMintPrice {});
+        }
+
+        if mint_price.is_empty() {
+            self.mint_price.remove(deps.storage);
+        } else {
+            self.mint_price.save(deps.storage, &mint_price)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_mint_price")
+            .add_attribute("sender", info.sender)
+            .add_attribute("mint_price", coins_to_string(&mint_price)))
+    }
+
+    /// Withdraws the fees paid to mint the POAPs, this action can be executed only from
+    /// the contract admin.
+    /// * `receiver` - Address that will receive the fees.
+    /// * `amount` - Fees to withdraw, if None all the collected fees are withdrawn.
+    pub fn withdraw_fees(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        receiver: String,
+        amount: Option<Vec<Coin>>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+        let receiver = deps.api.addr_validate(&receiver)?;
+
+        let amount = match amount {
+            Some(amount) => {
+                // Merge the coins with the same denom and drop the zero ones,
+                // so that each denom is checked against the collected fees once.
+                let mut amount = NativeBalance(amount);
+                amount.normalize();
+                amount.into_vec()
+            }
+            None => self.collected_fees(deps.storage)?,
+        };
+        for coin in &amount {
+            let collected = self
+                .collected_fees
+                .may_load(deps.storage, &coin.denom)?
+                .unwrap_or_default();
+            if coin.amount > collected {
+                return Err(ContractError::InsufficientCollectedFees {
+                    collected: Coin::new(collected.u128(), coin.denom.clone()),
+                });
+            }
+
+            let remaining = collected - coin.amount;
+            if remaining.is_zero() {
+                self.collected_fees.remove(deps.storage, &coin.denom);
+            } else {
+                self.collected_fees
+                    .save(deps.storage, &coin.denom, &remaining)?;
+            }
+        }
+
+        let mut response = Response::new()
+            .add_attribute("action", "withdraw_fees")
+            .add_attribute("sender", info.sender)
+            .add_attribute("receiver", &receiver)
+            .add_attribute("amount", coins_to_string(&amount));
+        if !amount.is_empty() {
+            response = response.add_message(BankMsg::Send {
+                to_address: receiver.to_string(),
+                amount,
+            });
+        }
+
+        Ok(response)
+    }
+
+    /// Registers a contract that is notified each time a POAP is minted, burned or transferred,
+    /// this action can be executed only from the contract admin.
+    /// Registering an already registered contract updates how its errors are handled.
+    /// * `contract` - Address of the contract.
+    /// * `ignore_errors` - If true a failure of the contract is ignored,
+    ///   otherwise it reverts the action that has notified the contract.
+    pub fn add_hook(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        contract: String,
+        ignore_errors: bool,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+        let contract = deps.api.addr_validate(&contract)?;
+        self.hooks.save(deps.storage, &contract, &ignore_errors)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "add_hook")
+            .add_attribute("sender", info.sender)
+            .add_attribute("contract", contract)
+            .add_attribute("ignore_errors", ignore_errors.to_string()))
+    }
+
+    /// Removes a contract registered with `AddHook`, this action can be executed only from
+    /// the contract admin.
+    /// * `contract` - Address of the contract.
+    pub fn remove_hook(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        contract: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+        let contract = deps.api.addr_validate(&contract)?;
+        if !self.hooks.has(deps.storage, &contract) {
+            return Err(ContractError::HookNotFound {
+                contract: contract.to_string(),
+            });
+        }
+        self.hooks.remove(deps.storage, &contract);
+
+        Ok(Response::new()
+            .add_attribute("action", "remove_hook")
+            .add_attribute("sender", info.sender)
+            .add_attribute("contract", contract))
+    }
+
+    /// Pauses all the operations that change the contract state, except the ones that manage
+    /// the contract ownership and the pause itself, this action can be executed only from
+    /// the contract admin or the pauser.
+    pub fn pause(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        let is_pauser = self
+            .pauser
+            .may_load(deps.storage)?
+            .is_some_and(|pauser| pauser == info.sender);
+        if !is_pauser && self.assert_is_admin(deps.storage, &info.sender).is_err() {
+            return Err(ContractError::PauseUnauthorized {});
+        }
+        self.is_paused.save(deps.storage, &true)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "pause")
+            .add_attribute("sender", info.sender))
+    }
+
+    /// Resumes the operations stopped with [`PoapContract::pause`], this action can be
+    /// executed only from the contract admin.
+    pub fn unpause(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+        self.is_paused.save(deps.storage, &false)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "unpause")
+            .add_attribute("sender", info.sender))
+    }
+
+    /// Updates the address that is allowed to pause the contract, this action can be
+    /// executed only from the contract admin.
+    /// * `pauser` - The new pauser address, if None only the admin can pause the contract.
+    pub fn update_pauser(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        pauser: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+        let new_pauser = pauser.map(|p| deps.api.addr_validate(&p)).transpose()?;
+        match &new_pauser {
+            Some(pauser) => self.pauser.save(deps.storage, pauser)?,
+            None => self.pauser.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "update_pauser")
+            .add_attribute("sender", info.sender)
+            .add_attribute(
+                "new_pauser",
+                new_pauser.map_or_else(|| "none".to_string(), |pauser| pauser.to_string()),
+            ))
+    }
+
+    /// Sets the configuration of the Desmos posts that announce the minted POAPs, this action
+    /// can be executed only from the contract admin.
+    /// * `announce` - The new configuration, if None the mints are not announced.
+    pub fn set_announce(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        announce: Option<AnnounceConfig>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+
+        match &announce {
+            Some(announce) => {
+                assert_valid_announce(announce)?;
+                self.announce.save(deps.storage, announce)?;
+            }
+            None => self.announce.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_announce")
+            .add_attribute("sender", info.sender)
+            .add_attribute(
+                "subspace_id",
+                announce.map_or_else(
+                    || "none".to_string(),
+                    |announce| announce.subspace_id.to_string(),
+                ),
+            ))
+    }
+
+    /// Sets the maximum number of POAPs that an address can own, this action can be
+    /// executed only from the contract admin.
+    /// * `limit` - The new per address limit, must be greater than zero.
+    pub fn set_per_address_limit(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        limit: u32,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+
+        if limit == 0 {
+            return Err(ContractError::InvalidPerAddressLimit {});
+        }
+        self.per_address_limit.save(deps.storage, &limit)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_per_address_limit")
+            .add_attribute("sender", info.sender)
+            .add_attribute("limit", limit.to_string()))
+    }
+
+    /// Creates a new event, this action can be executed only from the contract admin.
+    /// * `metadata_uri` - The URI where users can view the associated metadata for the event POAPs.
+    /// * `minter` - Additional address that is allowed to mint the event POAPs.
+    /// * `is_transferable` - true if the event POAPs can be transferred, false otherwise.
+    /// * `is_mintable` - true if the event POAPs can be minted from the users, false otherwise.
+    /// * `mint_start_time` - Block height or timestamp at which the minting of the event POAPs
+    ///   will be enabled.
+    /// * `mint_end_time` - Block height or timestamp at which the minting of the event POAPs
+    ///   will be disabled.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_event(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        metadata_uri: String,
+        minter: Option<String>,
+        is_transferable: bool,
+        is_mintable: bool,
+        mint_start_time: Option<Scheduled>,
+        mint_end_time: Option<Expiration>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+
+        let event_id = self.next_event_id.may_load(deps.storage)?.unwrap_or(1);
+        self.save_event(
+            deps,
+            event_id,
+            metadata_uri,
+            minter,
+            is_transferable,
+            is_mintable,
+            mint_start_time,
+            mint_end_time,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "create_event")
+            .add_attribute("sender", info.sender)
+            .add_attribute("event_id", event_id.to_string()))
+    }
+
+    /// Updates the configuration of an existing event, this action can be executed only from
+    /// the contract admin.
+    /// * `event_id` - Id of the event to update.
+    /// * `metadata_uri` - The URI where users can view the associated metadata for the event POAPs.
+    /// * `minter` - Additional address that is allowed to mint the event POAPs.
+    /// * `is_transferable` - true if the event POAPs can be transferred, false otherwise.
+    /// * `is_mintable` - true if the event POAPs can be minted from the users, false otherwise.
+    /// * `mint_start_time` - Block height or timestamp at which the minting of the event POAPs
+    ///   will be enabled.
+    /// * `mint_end_time` - Block height or timestamp at which the minting of the event POAPs
+    ///   will be disabled.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_event(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        event_id: u64,
+        metadata_uri: String,
+        minter: Option<String>,
+        is_transferable: bool,
+        is_mintable: bool,
+        mint_start_time: Option<Scheduled>,
+        mint_end_time: Option<Expiration>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+
+        let event = self.event_info(deps.storage, Some(event_id))?;
+        if event.metadata_uri != metadata_uri {
+            self.assert_metadata_not_frozen(deps.storage)?;
+        }
+        self.save_event(
+            deps,
+            event_id,
+            metadata_uri,
+            minter,
+            is_transferable,
+            is_mintable,
+            mint_start_time,
+            mint_end_time,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "update_event")
+            .add_attribute("sender", info.sender)
+            .add_attribute("event_id", event_id.to_string()))
+    }
+
+    /// Updates the metadata URI of the contract default event, this action can be executed only
+    /// from the contract admin.
+    /// * `metadata_uri` - The new metadata URI.
+    /// * `refresh_token_ids` - Ids of the POAPs whose `token_uri` will be recomputed.
+    pub fn update_metadata_uri(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        metadata_uri: String,
+        refresh_token_ids: Option<Vec<String>>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+        self.assert_metadata_not_frozen(deps.storage)?;
+        self.metadata_uri.save(deps.storage, &metadata_uri)?;
+
+        let refresh_token_ids = refresh_token_ids.unwrap_or_default();
+        for token_id in &refresh_token_ids {
+            let mut token = self.cw721_base.tokens.load(deps.storage, token_id)?;
+            let event_id = self.token_events.may_load(deps.storage, token_id)?;
+            let event_metadata_uri = self.event_info(deps.storage, event_id)?.metadata_uri;
+            token.token_uri = Some(token_uri(&event_metadata_uri, token_id));
+            self.cw721_base
+                .tokens
+                .save(deps.storage, token_id, &token)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "update_metadata_uri")
+            .add_attribute("sender", info.sender)
+            .add_attribute("metadata_uri", metadata_uri)
+            .add_attribute("refreshed_tokens", refresh_token_ids.len().to_string()))
+    }
+
+    /// Permanently prevents the metadata URIs from being updated, this action can be executed
+    /// only from the contract admin.
+    pub fn freeze_metadata(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+        self.metadata_frozen.save(deps.storage, &true)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "freeze_metadata")
+            .add_attribute("sender", info.sender))
+    }
+
+    /// Creates an airdrop job, this action can be executed only from the contract admin or
+    /// the event minter.
+    /// * `event_id` - Id of the event for which the POAPs will be minted,
+    ///   if None the POAPs are minted for the contract default event.
+    /// * `recipients` - First chunk of recipients of the airdrop.
+    pub fn create_airdrop(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        event_id: Option<u64>,
+        recipients: Vec<String>,
+        extension: T,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_event_minter_or_admin(deps.storage, &env.block, &info.sender, event_id)?;
+        self.consume_minter_quota(
+            deps.storage,
+            &info.sender,
+            event_id,
+            recipients.len() as u64,
+        )?;
+
+        let job_id = self.next_airdrop_id.may_load(deps.storage)?.unwrap_or(1);
+        self.next_airdrop_id.save(deps.storage, &(job_id + 1))?;
+
+        let mut job = AirdropJob {
+            event_id,
+            creator: info.sender.clone(),
+            extension,
+            total_recipients: 0,
+            processed: 0,
+            minted: 0,
+            failed: 0,
+            is_cancelled: false,
+            error: None,
+        };
+        self.store_airdrop_recipients(deps.storage, job_id, &mut job, recipients)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "create_airdrop")
+            .add_attribute("sender", info.sender)
+            .add_attribute("job_id", job_id.to_string())
+            .add_attribute("total_recipients", job.total_recipients.to_string()))
+    }
+
+    /// Appends a chunk of recipients to an airdrop job, this action can be executed only from
+    /// the contract admin or the job creator.
+    /// The recipients are counted against the quota of the job creator.
+    /// * `job_id` - Id of the airdrop job.
+    /// * `recipients` - Recipients to add.
+    pub fn add_airdrop_recipients(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        job_id: u64,
+        recipients: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        let mut job = self.active_airdrop_job(deps.storage, job_id)?;
+        // The recipients use the quota of the job creator, so the other minters can't add them.
+        if info.sender != job.creator && self.assert_is_admin(deps.storage, &info.sender).is_err() {
+            return Err(ContractError::MintUnauthorized {});
+        }
+        self.assert_is_event_minter_or_admin(deps.storage, &env.block, &info.sender, job.event_id)?;
+        self.consume_minter_quota(
+            deps.storage,
+            &job.creator,
+            job.event_id,
+            recipients.len() as u64,
+        )?;
+        self.store_airdrop_recipients(deps.storage, job_id, &mut job, recipients)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "add_airdrop_recipients")
+            .add_attribute("sender", info.sender)
+            .add_attribute("job_id", job_id.to_string())
+            .add_attribute("total_recipients", job.total_recipients.to_string()))
+    }
+
+    /// Mints the POAPs to the next recipients of an airdrop job, this action can be executed
+    /// from anyone.
+    /// The recipients that have an invalid address or already own the POAP are skipped
+    /// and recorded as failures of the job, while reaching the max supply stops the job.
+    /// The quota reserved for the recipients that don't receive a POAP is given back
+    /// to the job creator.
+    /// * `job_id` - Id of the airdrop job.
+    /// * `limit` - Maximum number of recipients to process.
+    pub fn process_airdrop(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        job_id: u64,
+        limit: u32,
+    ) -> Result<Response<C>, ContractError> {
+        nonpayable(&info)?;
+        if limit == 0 {
+            return Err(ContractError::InvalidAirdropLimit {});
+        }
+
+        let mut job = self.active_airdrop_job(deps.storage, job_id)?;
+        if job.processed >= job.total_recipients {
+            return Err(ContractError::AirdropAlreadyProcessed { job_id });
+        }
+
+        let end = job
+            .total_recipients
+            .min(job.processed.saturating_add(limit as u64));
+        let mut results = Vec::<MintToResult>::with_capacity((end - job.processed) as usize);
+        for index in job.processed..end {
+            let user = self
+                .airdrop_recipients
+                .load(deps.storage, (job_id, index))?;
+            let status = match self.mint_to_address(
+                deps.storage,
+                deps.api,
+                &env.block,
+                &job.creator,
+                &user,
+                job.event_id,
+                job.extension.clone(),
+                true,
+            ) {
+                Ok(status) => status,
+                // None of the remaining recipients can receive the POAP, so the job is stopped.
+                Err(err @ ContractError::MaxSupplyExceeded { .. }) => {
+                    job.error = Some(err.to_string());
+                    break;
+                }
+                Err(err) => return Err(err),
+            };
+
+            let result = MintToResult { user, status };
+            if let MintToStatus::Minted { .. } = result.status {
+                job.minted += 1;
+            } else {
+                job.failed += 1;
+                self.airdrop_failures
+                    .save(deps.storage, (job_id, index), &result)?;
+            }
+            results.push(result);
+            job.processed = index + 1;
+        }
+
+        let skipped = results
+            .iter()
+            .filter(|result| !matches!(result.status, MintToStatus::Minted { .. }))
+            .count() as u64;
+        let not_processed = if job.error.is_some() {
+            job.total_recipients - job.processed
+        } else {
+            0
+        };
+        self.refund_minter_quota(
+            deps.storage,
+            &job.creator,
+            job.event_id,
+            skipped + not_processed,
+        )?;
+        self.airdrop_jobs.save(deps.storage, job_id, &job)?;
+        let hooks = self.minted_hook_msgs(deps.storage, job.event_id, &results)?;
+        let minted: Vec<(&str, &str)> = results
+            .iter()
+            .filter_map(|result| match &result.status {
+                MintToStatus::Minted { token_id } => {
+                    Some((result.user.as_str(), token_id.as_str()))
+                }
+                _ => None,
+            })
+            .collect();
+        let announcements = self.announce_msgs(deps.storage, &env, &minted)?;
+
+        Ok(Response::new()
+            .add_submessages(hooks)
+            .add_messages(announcements)
+            .add_attribute("action", "process_airdrop")
+            .add_attribute("sender", info.sender)
+            .add_attribute("job_id", job_id.to_string())
+            .add_attribute("processed", job.processed.to_string())
+            .add_attribute("total_recipients", job.total_recipients.to_string())
+            .add_attribute("error", job.error.unwrap_or_else(|| "none".to_string()))
+            .set_data(to_binary(&MintToResponse { results })?))
+    }
+
+    /// Cancels an airdrop job, this action can be executed only from the contract admin or
+    /// the event minter.
+    /// The quota reserved for the recipients that have not been processed is given back
+    /// to the job creator.
+    /// * `job_id` - Id of the airdrop job.
+    pub fn cancel_airdrop(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        job_id: u64,
+    ) -> Result<Response<C>, ContractError> {
+        let mut job = self.active_airdrop_job(deps.storage, job_id)?;
+        self.assert_is_event_minter_or_admin(deps.storage, &env.block, &info.sender, job.event_id)?;
+
+        job.is_cancelled = true;
+        self.airdrop_jobs.save(deps.storage, job_id, &job)?;
+        self.refund_minter_quota(
+            deps.storage,
+            &job.creator,
+            job.event_id,
+            job.total_recipients - job.processed,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "cancel_airdrop")
+            .add_attribute("sender", info.sender)
+            .add_attribute("job_id", job_id.to_string())
+            .add_attribute("processed", job.processed.to_string())
+            .add_attribute("total_recipients", job.total_recipients.to_string()))
+    }
+
+    /// Reassigns a POAP to another user, this action can be executed only from the contract
+    /// admin or the event minter.
+    /// Allows to recover the soulbound POAPs of the users that have lost access to their keys.
+    /// * `token_id` - Id of the POAP to recover.
+    /// * `new_owner` - Address of the user that will receive the POAP.
+    pub fn recover(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        new_owner: String,
+    ) -> Result<Response<C>, ContractError> {
+        let event_id = self.token_events.may_load(deps.storage, &token_id)?;
+        self.assert_is_event_minter_or_admin(deps.storage, &env.block, &info.sender, event_id)?;
+
+        let new_owner = deps.api.addr_validate(&new_owner)?;
+        self.assert_user_below_per_address_limit(deps.storage, &new_owner, event_id)?;
+
+        let mut token = self.cw721_base.tokens.load(deps.storage, &token_id)?;
+        let previous_owner = token.owner;
+        token.owner = new_owner.clone();
+        token.approvals = vec![];
+        self.cw721_base
+            .tokens
+            .save(deps.storage, &token_id, &token)?;
+        self.update_holder_index(
+            deps.storage,
+            env.block.height,
+            Some(&previous_owner),
+            Some(&new_owner),
+        )?;
+        let hooks = self.hook_msgs(
+            deps.storage,
+            PoapHookMsg::Transferred {
+                token_id: token_id.clone(),
+                previous_owner: previous_owner.to_string(),
+                owner: new_owner.to_string(),
+                event_id,
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_submessages(hooks)
+            .add_attribute("action", "recover")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("previous_owner", previous_owner)
+            .add_attribute("new_owner", new_owner))
+    }
+
+    /// Burns a POAP and records the reason of its revocation, this action can be executed
+    /// only from the contract admin or the event minter.
+    /// * `token_id` - Id of the POAP to revoke.
+    /// * `reason` - Why the POAP has been revoked.
+    pub fn revoke_poap(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        reason: String,
+    ) -> Result<Response<C>, ContractError> {
+        let event_id = self.token_events.may_load(deps.storage, &token_id)?;
+        self.assert_is_event_minter_or_admin(deps.storage, &env.block, &info.sender, event_id)?;
+
+        let token = self.cw721_base.tokens.load(deps.storage, &token_id)?;
+        self.cw721_base.tokens.remove(deps.storage, &token_id)?;
+        self.cw721_base.decrement_tokens(deps.storage)?;
+        self.remove_poap_records(deps.storage, &token_id)?;
+        self.update_holder_index(deps.storage, env.block.height, Some(&token.owner), None)?;
+
+        self.revocations.save(
+            deps.storage,
+            &token_id,
+            &RevocationInfo {
+                owner: token.owner.clone(),
+                reason: reason.clone(),
+                revoked_by: info.sender.clone(),
+            },
+        )?;
+
+        let hooks = self.hook_msgs(
+            deps.storage,
+            PoapHookMsg::Burned {
+                token_id: token_id.clone(),
+                owner: token.owner.to_string(),
+                event_id,
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_submessages(hooks)
+            .add_attribute("action", "revoke_poap")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("owner", token.owner)
+            .add_attribute("reason", reason))
+    }
+
+    /// Validates and stores the configuration of an event.
+    #[allow(clippy::too_many_arguments)]
+    fn save_event(
+        &self,
+        deps: DepsMut,
+        event_id: u64,
+        metadata_uri: String,
+        minter: Option<String>,
+        is_transferable: bool,
+        is_mintable: bool,
+        mint_start_time: Option<Scheduled>,
+        mint_end_time: Option<Expiration>,
+    ) -> Result<(), ContractError> {
+        // Ensure that if we have both start time and end time the start time is lower then
+        // the end time.
+        assert_valid_mint_window(&mint_start_time, &mint_end_time)?;
+
+        let event = EventInfo {
+            metadata_uri,
+            minter: minter.map(|m| deps.api.addr_validate(&m)).transpose()?,
+            is_transferable,
+            is_mintable,
+            mint_start_time,
+            mint_end_time,
+        };
+        self.events.save(deps.storage, event_id, &event)?;
+
+        // Keep the serial number ahead of the stored events.
+        let next_event_id = self.next_event_id.may_load(deps.storage)?.unwrap_or(1);
+        if event_id >= next_event_id {
+            self.next_event_id.save(deps.storage, &(event_id + 1))?;
+        }
+
+        Ok(())
+    }
+}
+
+// Utility functions
+impl<'a, T, C, E, Q, X> PoapContract<'a, T, C, E, Q, X>
+where
+    T: Serialize + DeserializeOwned + Clone + Debug,
+    C: CustomMsg,
+    E: CustomMsg,
+    Q: CustomMsg,
+    X: PoapExtension<T, C, E, Q>,
+{
+    /// Computes the id of the next POAP to mint.
+    pub fn generate_poap_id(&self, storage: &dyn Storage) -> StdResult<String> {
+        Ok(self.next_poap_serial(storage)?.to_string())
+    }
+
+    /// Gets the serial number of the next POAP to mint, skipping the ones that are
+    /// already used by POAPs minted before the serial number was tracked.
+    fn next_poap_serial(&self, storage: &dyn Storage) -> StdResult<u64> {
+        let mut serial = self.next_poap_id.load(storage)?;
+        while self.cw721_base.tokens.has(storage, &serial.to_string()) {
+            serial += 1;
+        }
+
+        Ok(serial)
+    }
+
+    /// Gets the configuration of an event.
+    /// * `event_id` - Id of the event, if None the contract default event is returned.
+    pub fn event_info(
+        &self,
+        storage: &dyn Storage,
+        event_id: Option<u64>,
+    ) -> Result<EventInfo, ContractError> {
+        match event_id {
+            Some(event_id) => self
+                .events
+                .may_load(storage, event_id)?
+                .ok_or(ContractError::EventNotFound { event_id }),
+            None => Ok(EventInfo {
+                metadata_uri: self.metadata_uri.load(storage)?,
+                minter: self.minter.load(storage)?,
+                is_transferable: self.is_transferable.load(storage)?,
+                is_mintable: self.is_mintable.load(storage)?,
+                mint_start_time: self.mint_start_time.load(storage)?,
+                mint_end_time: self.mint_end_time.load(storage)?,
+            }),
+        }
+    }
+
+    /// Gets an airdrop job that has not been cancelled.
+    /// * `job_id` - Id of the airdrop job.
+    fn active_airdrop_job(
+        &self,
+        storage: &dyn Storage,
+        job_id: u64,
+    ) -> Result<AirdropJob<T>, ContractError> {
+        let job = self
+            .airdrop_jobs
+            .may_load(storage, job_id)?
+            .ok_or(ContractError::AirdropNotFound { job_id })?;
+        if job.is_cancelled {
+            return Err(ContractError::AirdropCancelled { job_id });
+        }
+        if let Some(error) = &job.error {
+            return Err(ContractError::AirdropFailed {
+                job_id,
+                error: error.clone(),
+            });
+        }
+
+        Ok(job)
+    }
+
+    /// Appends a chunk of recipients to an airdrop job and stores the updated job.
+    /// * `job_id` - Id of the airdrop job.
+    /// * `job` - The airdrop job.
+    /// * `recipients` - Recipients to add.
+    fn store_airdrop_recipients(
+        &self,
+        storage: &mut dyn Storage,
+        job_id: u64,
+        job: &mut AirdropJob<T>,
+        recipients: Vec<String>,
+    ) -> StdResult<()> {
+        for recipient in recipients {
+            self.airdrop_recipients
+                .save(storage, (job_id, job.total_recipients), &recipient)?;
+            job.total_recipients += 1;
+        }
+
+        self.airdrop_jobs.save(storage, job_id, job)
+    }
+
+    /// Removes the records that the contract keeps about a POAP that has been burned.
+    /// * `token_id` - Id of the burned POAP.
+    fn remove_poap_records(&self, storage: &mut dyn Storage, token_id: &str) -> StdResult<()> {
+        self.mint_info.remove(storage, token_id);
+        self.transferred_tokens.remove(storage, token_id);
+
+        // Remove the POAP from the tokens of its event.
+        if let Some(event_id) = self.token_events.may_load(storage, token_id)? {
+            self.token_events.remove(storage, token_id);
+            self.event_tokens.remove(storage, (event_id, token_id));
+        }
+
+        Ok(())
+    }
+
+    /// Updates the snapshots of the holder balances and of the total supply
+    /// after a POAP has changed owner.
+    /// * `height` - Height of the block in which the POAP has changed owner.
+    /// * `from` - Previous owner of the POAP, None if the POAP has been minted.
+    /// * `to` - New owner of the POAP, None if the POAP has been burned.
+    pub fn update_holder_index(
+        &self,
+        storage: &mut dyn Storage,
+        height: u64,
+        from: Option<&Addr>,
+        to: Option<&Addr>,
+    ) -> StdResult<()> {
+        match from {
+            Some(from) => {
+                let balance = self
+                    .holder_balances
+                    .may_load(storage, from)?
+                    .unwrap_or_default()
+                    .saturating_sub(1);
+                if balance == 0 {
+                    self.holder_balances.remove(storage, from, height)?;
+                } else {
+                    self.holder_balances.save(storage, from, &balance, height)?;
+                }
+            }
+            None => {
+                self.total_supply
+                    .update(storage, height, |supply| -> StdResult<_> {
+                        Ok(supply.unwrap_or_default() + 1)
+                    })?;
+            }
+        }
+
+        match to {
+            Some(to) => {
+                self.holder_balances
+                    .update(storage, to, height, |balance| -> StdResult<_> {
+                        Ok(balance.unwrap_or_default() + 1)
+                    })?;
+            }
+            None => {
+                self.total_supply
+                    .update(storage, height, |supply| -> StdResult<_> {
+                        Ok(supply.unwrap_or_default().saturating_sub(1))
+                    })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Builds the messages that announce the minted POAPs, if the contract has
+    /// an announce configuration.
+    /// * `minted` - Owner and id of each minted POAP.
+    fn announce_msgs(
+        &self,
+        storage: &dyn Storage,
+        env: &Env,
+        minted: &[(&str, &str)],
+    ) -> StdResult<Vec<CosmosMsg<C>>> {
+        let announce = match self.announce.may_load(storage)? {
+            Some(announce) => announce,
+            None => return Ok(vec![]),
+        };
+
+        let mut msgs = Vec::new();
+        for (owner, token_id) in minted {
+            msgs.extend(
+                self.extension
+                    .announce_mint(env, &announce, owner, token_id)?,
+            );
+        }
+
+        Ok(msgs)
+    }
+
+    /// Builds the messages that notify the registered hook contracts.
+    /// The messages of the contracts that ignore the errors reply on error,
+    /// so that a failure is handled by [`PoapContract::reply`] instead of reverting the action.
+    /// * `msg` - The notification to send.
+    pub fn hook_msgs(&self, storage: &dyn Storage, msg: PoapHookMsg) -> StdResult<Vec<SubMsg<C>>> {
+        self.hooks
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (contract, ignore_errors) = item?;
+                let hook_msg = msg.clone().into_cosmos_msg(contract)?;
+                Ok(if ignore_errors {
+                    SubMsg::reply_on_error(hook_msg, HOOK_REPLY_ID)
+                } else {
+                    SubMsg::new(hook_msg)
+                })
+            })
+            .collect()
+    }
+
+    /// Builds the messages that notify the registered hook contracts of the POAPs
+    /// minted with a batch.
+    /// * `event_id` - Id of the event for which the POAPs have been minted.
+    /// * `results` - Outcome of the mint for each user of the batch.
+    fn minted_hook_msgs(
+        &self,
+        storage: &dyn Storage,
+        event_id: Option<u64>,
+        results: &[MintToResult],
+    ) -> StdResult<Vec<SubMsg<C>>> {
+        let mut hooks = Vec::new();
+        for result in results {
+            if let MintToStatus::Minted { token_id } = &result.status {
+                hooks.extend(self.hook_msgs(
+                    storage,
+                    PoapHookMsg::Minted {
+                        token_id: token_id.clone(),
+                        owner: result.user.clone(),
+                        event_id,
+                    },
+                )?);
+            }
+        }
+
+        Ok(hooks)
+    }
+
+    /// Gets the fees paid to mint the POAPs that have not been withdrawn yet.
+    pub fn collected_fees(&self, storage: &dyn Storage) -> StdResult<Vec<Coin>> {
+        self.collected_fees
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+            .collect()
+    }
+
+    /// Gets the number of POAPs minted so far, including the ones that have been burned.
+    pub fn issued_poaps(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self.next_poap_id.load(storage)? - 1)
+    }
+
+    /// Gets the rules that the users must satisfy to mint a POAP.
+    pub fn eligibility_rules(&self, storage: &dyn Storage) -> StdResult<Vec<EligibilityRule>> {
+        Ok(self
+            .eligibility_rules
+            .may_load(storage)?
+            .unwrap_or_default())
+    }
+
+    /// Gets the maximum number of POAPs that an address can own.
+    pub fn per_address_limit(&self, storage: &dyn Storage) -> StdResult<u32> {
+        Ok(self
+            .per_address_limit
+            .may_load(storage)?
+            .unwrap_or(DEFAULT_PER_ADDRESS_LIMIT))
+    }
+
+    /// Gets the number of claim codes that have not been used yet.
+    pub fn claim_codes_count(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self
+            .claim_codes_count
+            .may_load(storage)?
+            .unwrap_or_default())
+    }
+
+    /// Mint a POAP to an user.
+    /// * `block` - Block in which the POAP is minted.
+    /// * `minted_by` - Address that has sent the message that is minting the POAP.
+    /// * `owner` - User for whom the POAP will be minted.
+    /// * `event_id` - Id of the event for which the POAP will be minted,
+    ///   if None the POAP is minted for the contract default event.
+    pub fn mint_to_user(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        minted_by: &Addr,
+        owner: &Addr,
+        event_id: Option<u64>,
+        extension: T,
+    ) -> Result<String, ContractError> {
+        // Check if this user can receive another poap.
+        self.assert_max_supply(storage, 1)?;
+        self.assert_user_below_per_address_limit(storage, owner, event_id)?;
+
+        let mint_info = MintInfo {
+            minted_at_height: block.height,
+            minted_at_time: block.time,
+            minted_by: minted_by.clone(),
+        };
+        self.extension
+            .before_mint(storage, owner, event_id, &mint_info, &extension)?;
+
+        // Generate the token id
+        let serial = self.next_poap_serial(storage)?;
+        let token_id = serial.to_string();
+
+        // Create the token
+        let metadata_uri = self.event_info(storage, event_id)?.metadata_uri;
+        let token = cw721_base::state::TokenInfo {
+            owner: owner.clone(),
+            approvals: vec![],
+            token_uri: Some(token_uri(&metadata_uri, &token_id)),
+            extension,
+        };
+        self.cw721_base
+            .tokens
+            .update(storage, &token_id, |old| match old {
+                Some(_) => Err(ContractError::Claimed {}),
+                None => Ok(token),
+            })?;
+
+        self.cw721_base.increment_tokens(storage)?;
+        self.next_poap_id.save(storage, &(serial + 1))?;
+        self.mint_info.save(storage, &token_id, &mint_info)?;
+        self.update_holder_index(storage, block.height, None, Some(owner))?;
+
+        if let Some(event_id) = event_id {
+            self.token_events.save(storage, &token_id, &event_id)?;
+            self.event_tokens
+                .save(storage, (event_id, token_id.as_str()), &Empty {})?;
+        }
+
+        Ok(token_id)
+    }
+
+    /// Mint a POAP to an user address that has not been validated yet.
+    /// * `block` - Block in which the POAP is minted.
+    /// * `minted_by` - Address that has sent the message that is minting the POAP.
+    /// * `user` - Address of the user for whom the POAP will be minted.
+    /// * `event_id` - Id of the event for which the POAP will be minted,
+    ///   if None the POAP is minted for the contract default event.
+    /// * `skip_invalid` - If true, an invalid address or a user that already owns the POAP
+    ///   are reported in the returned status instead of failing.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_to_address(
+        &self,
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        block: &BlockInfo,
+        minted_by: &Addr,
+        user: &str,
+        event_id: Option<u64>,
+        extension: T,
+        skip_invalid: bool,
+    ) -> Result<MintToStatus, ContractError> {
+        let user_addr = match api.addr_validate(user) {
+            Ok(user_addr) => user_addr,
+            Err(_) if skip_invalid => return Ok(MintToStatus::InvalidAddress {}),
+            Err(err) => return Err(err.into()),
+        };
+
+        match self.mint_to_user(storage, block, minted_by, &user_addr, event_id, extension) {
+            Ok(token_id) => Ok(MintToStatus::Minted { token_id }),
+            Err(ContractError::PoapAlreadyMinted { .. }) if skip_invalid => {
+                Ok(MintToStatus::AlreadyOwned {})
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Asserts that the contract is not paused.
+    pub fn assert_not_paused(&self, storage: &dyn Storage) -> Result<(), ContractError> {
+        if self.is_paused.may_load(storage)?.unwrap_or_default() {
+            return Err(ContractError::Paused {});
+        }
+
+        Ok(())
+    }
+
+    /// Asserts that the provided address is the contract admin.
+    /// * `sender` - Address that will be checked.
+    pub fn assert_is_admin(
+        &self,
+        storage: &dyn Storage,
+        sender: &Addr,
+    ) -> Result<(), ContractError> {
+        cw_ownable::assert_owner(storage, sender).map_err(|e| ContractError::Ownership(e))
+    }
+
+    /// Asserts that the provided address is the contract minter or an active minter
+    /// registered with `AddMinter`.
+    /// * `block` - Current block, used to check the expiration of the registered minters.
+    /// * `sender` - Address that will be checked.
+    pub fn assert_is_minter(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        sender: &Addr,
+    ) -> Result<(), ContractError> {
+        if self.minter.load(storage)?.as_ref() == Some(sender) {
+            return Ok(());
+        }
+
+        self.assert_is_registered_minter(storage, block, sender)
+    }
+
+    /// Asserts that the provided address is a minter registered with `AddMinter`
+    /// that has not expired yet.
+    /// * `block` - Current block, used to check the expiration of the minter.
+    /// * `sender` - Address that will be checked.
+    pub fn assert_is_registered_minter(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        sender: &Addr,
+    ) -> Result<(), ContractError> {
+        match self.minters.may_load(storage, sender)? {
+            Some(minter) if !minter.expiration.is_some_and(|e| e.is_expired(block)) => Ok(()),
+            _ => Err(ContractError::MintUnauthorized {}),
+        }
+    }
+
+    /// Asserts that the provided address is the minter of an event, an active minter
+    /// registered with `AddMinter` or the contract admin.
+    /// * `block` - Current block, used to check the expiration of the registered minters.
+    /// * `sender` - Address that will be checked.
+    /// * `event_id` - Id of the event, if None the contract default event is checked.
+    pub fn assert_is_event_minter_or_admin(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        sender: &Addr,
+        event_id: Option<u64>,
+    ) -> Result<(), ContractError> {
+        let event = self.event_info(storage, event_id)?;
+        if event.minter.as_ref() == Some(sender) || self.assert_is_admin(storage, sender).is_ok() {
+            return Ok(());
+        }
+
+        self.assert_is_registered_minter(storage, block, sender)
+    }
+
+    /// Consumes the mint quota of a minter registered with `AddMinter`.
+    /// The contract admin and the event minter don't have a quota.
+    /// * `sender` - Address of the minter.
+    /// * `event_id` - Id of the event for which the POAPs are minted.
+    /// * `amount` - Number of POAPs minted.
+    pub fn consume_minter_quota(
+        &self,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        event_id: Option<u64>,
+        amount: u64,
+    ) -> Result<(), ContractError> {
+        let event = self.event_info(storage, event_id)?;
+        if event.minter.as_ref() == Some(sender) || self.assert_is_admin(storage, sender).is_ok() {
+            return Ok(());
+        }
+
+        if let Some(mut minter) = self.minters.may_load(storage, sender)? {
+            if let Some(quota) = minter.quota {
+                let remaining = quota.saturating_sub(minter.minted);
+                if amount > remaining {
+                    return Err(ContractError::MinterQuotaExceeded {
+                        minter: sender.to_string(),
+                        remaining,
+                    });
+                }
+            }
+            minter.minted += amount;
+            self.minters.save(storage, sender, &minter)?;
+        }
+
+        Ok(())
+    }
+
+    /// Gives back to a minter registered with `AddMinter` the quota consumed for POAPs
+    /// that have not been minted.
+    /// * `minter` - Address of the minter.
+    /// * `event_id` - Id of the event for which the POAPs were going to be minted.
+    /// * `amount` - Number of POAPs that have not been minted.
+    pub fn refund_minter_quota(
+        &self,
+        storage: &mut dyn Storage,
+        minter: &Addr,
+        event_id: Option<u64>,
+        amount: u64,
+    ) -> Result<(), ContractError> {
+        let event = self.event_info(storage, event_id)?;
+        if amount == 0
+            || event.minter.as_ref() == Some(minter)
+            || self.assert_is_admin(storage, minter).is_ok()
+        {
+            return Ok(());
+        }
+
+        if let Some(mut minter_info) = self.minters.may_load(storage, minter)? {
+            minter_info.minted = minter_info.minted.saturating_sub(amount);
+            self.minters.save(storage, minter, &minter_info)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check whether a user owns less POAPs of an event than the per address limit.
+    /// * `user` - Address that will be checked.
+    /// * `event_id` - Id of the event, if None the contract default event is checked.
+    pub fn assert_user_below_per_address_limit(
+        &self,
+        storage: &dyn Storage,
+        user: &Addr,
+        event_id: Option<u64>,
+    ) -> Result<(), ContractError> {
+        let limit = self.per_address_limit(storage)?;
+        let owned_tokens = self.cw721_base.tokens.idx.owner.prefix(user.clone()).keys(
+            storage,
+            None,
+            None,
+            Order::Ascending,
+        );
+
+        let mut owned: u32 = 0;
+        for token_id in owned_tokens {
+            if self.token_events.may_load(storage, &token_id?)? == event_id {
+                owned += 1;
+            }
+
+            if owned >= limit {
+                return Err(ContractError::PoapAlreadyMinted {
+                    user: user.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the provided amount of POAPs can be minted without exceeding the max supply.
+    /// * `amount` - Number of POAPs to mint.
+    pub fn assert_max_supply(
+        &self,
+        storage: &dyn Storage,
+        amount: u64,
+    ) -> Result<(), ContractError> {
+        if let Some(max_supply) = self.max_supply.may_load(storage)? {
+            // The burned POAPs are still counted, so that they can't be minted again.
+            let remaining = max_supply.saturating_sub(self.issued_poaps(storage)?);
+            if amount > remaining {
+                return Err(ContractError::MaxSupplyExceeded {
+                    max_supply,
+                    remaining,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Asserts if an user can mint a POAP of an event.
+    /// * `querier` - Querier used to check the eligibility rules.
+    /// * `user` - Address that will be checked.
+    /// * `event` - Event for which the POAP will be minted.
+    pub fn assert_user_can_mint(
+        &self,
+        storage: &dyn Storage,
+        querier: &dyn Querier,
+        user: &Addr,
+        env: &Env,
+        event: &EventInfo,
     ) -> Result<(), ContractError> {
         // Check if the user is the minter.
-        if self.assert_is_minter(storage, user).is_ok() {
+        if event.minter.as_ref() == Some(user) {
             // The minter can always perform the mint operation.
             return Ok(());
         }
@@ -415,22 +2622,109 @@ where
         }
 
         // Check if mint is enabled
-        if !self.is_mintable.load(storage)? {
+        if !event.is_mintable {
             return Err(ContractError::MintDisabled {});
         }
 
+        self.assert_mint_time(event, env)?;
+        self.assert_user_is_eligible(storage, querier, user)
+    }
+
+    /// Asserts that an user satisfies all the eligibility rules.
+    /// * `querier` - Querier used to perform the Desmos custom queries.
+    /// * `user` - Address that will be checked.
+    pub fn assert_user_is_eligible(
+        &self,
+        storage: &dyn Storage,
+        querier: &dyn Querier,
+        user: &Addr,
+    ) -> Result<(), ContractError> {
+        for rule in self.eligibility_rules(storage)? {
+            match rule {
+                EligibilityRule::HasProfile {} => {
+                    ProfilesQuerier::new(querier)
+                        .query_profile(user.clone())
+                        .map_err(|error| {
+                            if is_not_found_error(&error) {
+                                ContractError::ProfileRequired {
+                                    user: user.to_string(),
+                                }
+                            } else {
+                                error.into()
+                            }
+                        })?;
+                }
+                EligibilityRule::HasAppLink { application } => {
+                    let mut has_app_link = false;
+                    for app_link in ProfilesQuerier::new(querier).iterate_application_links(
+                        Some(user.clone()),
+                        Some(application.clone()),
+                        None,
+                        10,
+                    ) {
+                        if app_link?.state == ApplicationLinkState::VerificationSuccess {
+                            has_app_link = true;
+                            break;
+                        }
+                    }
+
+                    if !has_app_link {
+                        return Err(ContractError::AppLinkRequired {
+                            user: user.to_string(),
+                            application,
+                        });
+                    }
+                }
+                EligibilityRule::SubspaceMember {
+                    subspace_id,
+                    permission,
+                } => {
+                    // Query the permissions of the root section, that also include the ones
+                    // granted through the user groups.
+                    let permissions = SubspacesQuerier::new(querier)
+                        .query_user_permissions(subspace_id.u64(), 0, user.clone())?
+                        .permissions;
+                    if !permissions.contains(&permission) {
+                        return Err(ContractError::SubspaceMembershipRequired {
+                            user: user.to_string(),
+                            subspace_id: subspace_id.u64(),
+                            permission,
+                        });
+                    }
+                }
+                EligibilityRule::UserGroupMember {
+                    subspace_id,
+                    group_id,
+                } => {
+                    if !is_user_group_member(querier, subspace_id.u64(), group_id, user)? {
+                        return Err(ContractError::UserGroupMembershipRequired {
+                            user: user.to_string(),
+                            subspace_id: subspace_id.u64(),
+                            group_id,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Asserts that the current block time is inside the mint period of an event.
+    /// * `event` - Event for which the POAP will be minted.
+    pub fn assert_mint_time(&self, event: &EventInfo, env: &Env) -> Result<(), ContractError> {
         // Check if we have a mint start time
-        if let Some(start_time) = self.mint_start_time.load(storage)? {
+        if let Some(start_time) = &event.mint_start_time {
             // Check if the event has started.
-            if start_time.gt(&env.block.time) {
+            if !start_time.is_triggered(&env.block) {
                 return Err(ContractError::MintTimeNotStarted {});
             }
         }
 
         // Check if we have a mint end time
-        if let Some(end_time) = self.mint_end_time.load(storage)? {
+        if let Some(end_time) = &event.mint_end_time {
             // Check if the event is still in progress.
-            if env.block.time.ge(&end_time) {
+            if end_time.is_expired(&env.block) {
                 return Err(ContractError::MintTimeAlreadyEnded {});
             }
         }
@@ -438,13 +2732,277 @@ where
         Ok(())
     }
 
-    /// Asserts if the transfer is allowed.
-    pub fn assert_is_transferable(&self, storage: &dyn Storage) -> Result<(), ContractError> {
-        let is_transferable = self.is_transferable.load(storage)?;
-        if !is_transferable {
+    /// Asserts that a user can be indicated as referrer by the user that is minting a POAP.
+    /// * `minter` - Address of the user that is minting the POAP.
+    /// * `referrer` - Address of the referrer.
+    pub fn assert_valid_referrer(
+        &self,
+        storage: &dyn Storage,
+        minter: &Addr,
+        referrer: &Addr,
+    ) -> Result<(), ContractError> {
+        if referrer == minter {
+            return Err(ContractError::SelfReferral {});
+        }
+
+        let balance = self.holder_balances.may_load(storage, referrer)?;
+        if balance.unwrap_or_default() == 0 {
+            return Err(ContractError::ReferrerNotHolder {
+                referrer: referrer.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Increases the number of users referred by a referrer.
+    /// * `referrer` - Address of the referrer.
+    pub fn record_referral(&self, storage: &mut dyn Storage, referrer: &Addr) -> StdResult<()> {
+        let count = self
+            .referrals
+            .may_load(storage, referrer)?
+            .unwrap_or_default();
+        self.referrers_by_count.remove(storage, (count, referrer));
+        self.referrers_by_count
+            .save(storage, (count + 1, referrer), &Empty {})?;
+        self.referrals.save(storage, referrer, &(count + 1))
+    }
+
+    /// Asserts that the metadata URIs have not been frozen.
+    pub fn assert_metadata_not_frozen(&self, storage: &dyn Storage) -> Result<(), ContractError> {
+        if self.metadata_frozen.may_load(storage)?.unwrap_or(false) {
+            return Err(ContractError::MetadataFrozen {});
+        }
+
+        Ok(())
+    }
+
+    /// Asserts if the transfer of a POAP is allowed by its event.
+    /// * `token_id` - Id of the POAP to transfer.
+    pub fn assert_is_transferable(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        let event_id = self.token_events.may_load(storage, token_id)?;
+        if !self.event_info(storage, event_id)?.is_transferable {
             return Err(ContractError::TransferDisabled {});
         }
 
         Ok(())
     }
+
+    /// Asserts if the transfer policy allows to move a POAP to a user or to approve
+    /// a user to move it.
+    /// * `block` - Block in which the POAP is moved or approved.
+    /// * `token_id` - Id of the POAP, None if an operator is approved for all the POAPs of an owner.
+    /// * `recipient` - Address of the user that receives the POAP or that is approved.
+    /// * `is_approval` - If true, `recipient` is approved to move the POAP instead of receiving it.
+    pub fn assert_transfer_policy(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        token_id: Option<&str>,
+        recipient: &Addr,
+        is_approval: bool,
+    ) -> Result<(), ContractError> {
+        match self.transfer_policy.may_load(storage)? {
+            Some(TransferPolicy::Deadline { deadline }) if deadline.is_expired(block) => {
+                Err(ContractError::TransferDeadlineExpired {})
+            }
+            Some(TransferPolicy::SingleTransfer {}) => match token_id {
+                Some(token_id) if self.transferred_tokens.has(storage, token_id) => {
+                    Err(ContractError::AlreadyTransferred {
+                        token_id: token_id.to_string(),
+                    })
+                }
+                _ => Ok(()),
+            },
+            Some(TransferPolicy::NonHoldersOnly {})
+                if !is_approval && self.holder_balances.may_load(storage, recipient)?.is_some() =>
+            {
+                Err(ContractError::RecipientAlreadyHolder {
+                    recipient: recipient.to_string(),
+                })
+            }
+            Some(TransferPolicy::AllowlistedContracts { contracts })
+                if !contracts.contains(&recipient.to_string()) =>
+            {
+                Err(ContractError::RecipientNotAllowlisted {
+                    recipient: recipient.to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Asserts that the mint period starts before it ends.
+/// A start and an end expressed one as block height and the other as timestamp can't be
+/// compared, so they are always considered valid.
+/// * `start` - Block height or timestamp at which the minting will be enabled.
+/// * `end` - Block height or timestamp at which the minting will be disabled.
+fn assert_valid_mint_window(
+    start: &Option<Scheduled>,
+    end: &Option<Expiration>,
+) -> Result<(), ContractError> {
+    let is_valid = match (start, end) {
+        (Some(Scheduled::AtHeight(start)), Some(Expiration::AtHeight(end))) => start < end,
+        (Some(Scheduled::AtTime(start)), Some(Expiration::AtTime(end))) => start < end,
+        _ => true,
+    };
+
+    if !is_valid {
+        return Err(ContractError::InvalidTimestampValues {});
+    }
+
+    Ok(())
+}
+
+/// Asserts that the announce configuration has a text to post.
+/// * `announce` - The announce configuration to validate.
+fn assert_valid_announce(announce: &AnnounceConfig) -> Result<(), ContractError> {
+    if announce.text.trim().is_empty() {
+        return Err(ContractError::InvalidAnnounceText {});
+    }
+
+    Ok(())
+}
+
+/// Validates the addresses of the contracts allowlisted by a transfer policy.
+/// * `policy` - The transfer policy to validate.
+fn validate_transfer_policy(api: &dyn Api, policy: TransferPolicy) -> StdResult<TransferPolicy> {
+    match policy {
+        TransferPolicy::AllowlistedContracts { contracts } => {
+            let contracts = contracts
+                .iter()
+                .map(|contract| api.addr_validate(contract).map(|addr| addr.to_string()))
+                .collect::<StdResult<_>>()?;
+            Ok(TransferPolicy::AllowlistedContracts { contracts })
+        }
+        policy => Ok(policy),
+    }
+}
+
+/// Checks that the funds sent with a mint cover the mint price and gets the excess
+/// that must be refunded to the sender.
+/// * `mint_price` - The mint price.
+/// * `funds` - Funds sent with the mint.
+fn mint_price_refund(mint_price: &[Coin], funds: &[Coin]) -> Result<Vec<Coin>, ContractError> {
+    let paid = |denom: &str| {
+        funds
+            .iter()
+            .find(|coin| coin.denom == denom)
+            .map_or(Uint128::zero(), |coin| coin.amount)
+    };
+    if mint_price
+        .iter()
+        .any(|coin| paid(&coin.denom) < coin.amount)
+    {
+        return Err(ContractError::InsufficientMintPrice {
+            mint_price: coins_to_string(mint_price),
+        });
+    }
+
+    Ok(funds
+        .iter()
+        .filter_map(|fund| {
+            let price = mint_price
+                .iter()
+                .find(|coin| coin.denom == fund.denom)
+                .map_or(Uint128::zero(), |coin| coin.amount);
+            (fund.amount > price).then(|| Coin {
+                denom: fund.denom.clone(),
+                amount: fund.amount - price,
+            })
+        })
+        .collect())
+}
+
+/// Formats a list of coins as a comma separated string, "none" if the list is empty.
+fn coins_to_string(coins: &[Coin]) -> String {
+    if coins.is_empty() {
+        return "none".to_string();
+    }
+
+    coins
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Builds the URI of a POAP replacing the `{token_id}` placeholder of the metadata URI.
+/// * `metadata_uri` - Metadata URI of the POAP event.
+/// * `token_id` - Id of the POAP.
+fn token_uri(metadata_uri: &str, token_id: &str) -> String {
+    metadata_uri.replace(TOKEN_ID_PLACEHOLDER, token_id)
+}
+
+/// Checks if a leaf is part of the Merkle tree with the provided root.
+/// The leaves are the SHA-256 hashes of the values and each parent node is the SHA-256
+/// hash of the concatenation of its children, sorted in ascending order.
+/// * `root` - Root of the Merkle tree.
+/// * `value` - Value from which the leaf has been computed.
+/// * `proof` - Hashes of the sibling nodes from the leaf up to the root.
+fn verify_merkle_proof(root: &[u8], value: &[u8], proof: &[Binary]) -> bool {
+    let mut hash: [u8; SHA256_HASH_LENGTH] = Sha256::digest(value).into();
+    for sibling in proof {
+        if sibling.len() != SHA256_HASH_LENGTH {
+            return false;
+        }
+
+        let (left, right) = if hash.as_slice() <= sibling.as_slice() {
+            (hash.as_slice(), sibling.as_slice())
+        } else {
+            (sibling.as_slice(), hash.as_slice())
+        };
+        hash = Sha256::new()
+            .chain_update(left)
+            .chain_update(right)
+            .finalize()
+            .into();
+    }
+
+    hash.as_slice() == root
+}
+
+/// Checks if an user is a member of a Desmos subspace user group.
+/// The members are stored ordered by their address, so the user is a member only if
+/// it is the first one of the page that starts from its address.
+/// * `querier` - Querier used to perform the Desmos custom queries.
+/// * `subspace_id` - Id of the subspace.
+/// * `group_id` - Id of the user group.
+/// * `user` - Address that will be checked.
+fn is_user_group_member(
+    querier: &dyn Querier,
+    subspace_id: u64,
+    group_id: u32,
+    user: &Addr,
+) -> StdResult<bool> {
+    let members = SubspacesQuerier::new(querier)
+        .query_user_group_members(
+            subspace_id,
+            group_id,
+            Some(PageRequest {
+                key: Some(Binary::from(user.as_bytes())),
+                offset: None,
+                limit: Uint64::new(1),
+                count_total: false,
+                reverse: false,
+            }),
+        )?
+        .members;
+
+    Ok(members.first() == Some(user))
+}
+
+/// Checks if a Desmos custom query has failed because the requested object doesn't exist.
+/// * `error` - Error returned by the query.
+fn is_not_found_error(error: &StdError) -> bool {
+    match error {
+        StdError::NotFound { .. } => true,
+        StdError::GenericErr { msg, .. } => msg.to_lowercase().contains("not found"),
+        _ => false,
+    }
 }
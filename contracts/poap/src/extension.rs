@@ -0,0 +1,128 @@
+use crate::msg::{AnnounceConfig, MintInfo};
+use crate::ContractError;
+use cosmwasm_std::{
+    Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage,
+};
+use desmos_bindings::msg::DesmosMsg;
+use desmos_bindings::posts::models::{Entities, ReplySetting, TextTag};
+use desmos_bindings::posts::msg::PostsMsg;
+
+/// Placeholder of the announce text that is replaced with the address of the POAP owner.
+const USER_PLACEHOLDER: &str = "{user}";
+/// Placeholder of the announce text that is replaced with the id of the POAP.
+const TOKEN_ID_PLACEHOLDER: &str = "{token_id}";
+
+/// Custom behaviour that the contracts embedding [`crate::PoapContract`] as a library can plug in.
+/// All the methods except [`PoapExtension::query`] have a no-op default implementation,
+/// so an implementation only needs to override the ones it is interested in.
+pub trait PoapExtension<T, C, E, Q> {
+    /// Handles the [`crate::ExecuteMsg::Extension`] messages.
+    /// * `msg` - The custom execute message.
+    fn execute(
+        &self,
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: E,
+    ) -> Result<Response<C>, ContractError> {
+        Ok(Response::default())
+    }
+
+    /// Handles the [`crate::QueryMsg::Extension`] queries.
+    /// By default the extension queries are rejected.
+    /// * `msg` - The custom query message.
+    fn query(&self, _deps: Deps, _env: Env, _msg: Q) -> Result<Binary, ContractError> {
+        Err(ContractError::ExtensionQueryNotSupported {})
+    }
+
+    /// Validates a POAP before it is minted, an error prevents the POAP from being minted.
+    /// This is called for all the POAPs, regardless of the message used to mint them.
+    /// * `owner` - User for whom the POAP will be minted.
+    /// * `event_id` - Id of the event for which the POAP will be minted,
+    ///   None for the contract default event.
+    /// * `mint_info` - Information about the mint of the POAP.
+    /// * `extension` - Extension of the POAP.
+    fn before_mint(
+        &self,
+        _storage: &dyn Storage,
+        _owner: &Addr,
+        _event_id: Option<u64>,
+        _mint_info: &MintInfo,
+        _extension: &T,
+    ) -> Result<(), ContractError> {
+        Ok(())
+    }
+
+    /// Builds the messages that announce the mint of a POAP.
+    /// This is called for all the minted POAPs when the contract has an announce configuration.
+    /// * `announce` - Configuration of the announcement.
+    /// * `owner` - Address of the user for whom the POAP has been minted.
+    /// * `token_id` - Id of the minted POAP.
+    fn announce_mint(
+        &self,
+        _env: &Env,
+        _announce: &AnnounceConfig,
+        _owner: &str,
+        _token_id: &str,
+    ) -> StdResult<Vec<CosmosMsg<C>>> {
+        Ok(vec![])
+    }
+}
+
+/// [`PoapExtension`] that doesn't add any behaviour, used by the contract entry points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NoopExtension;
+
+impl<T, C, E, Q> PoapExtension<T, C, E, Q> for NoopExtension {}
+
+/// [`PoapExtension`] used by the Desmos entry points, that announces the minted POAPs
+/// with a Desmos post that mentions their owner.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DesmosExtension;
+
+impl<T, E, Q> PoapExtension<T, DesmosMsg, E, Q> for DesmosExtension {
+    fn announce_mint(
+        &self,
+        env: &Env,
+        announce: &AnnounceConfig,
+        owner: &str,
+        token_id: &str,
+    ) -> StdResult<Vec<CosmosMsg<DesmosMsg>>> {
+        let text = announce.text.replace(TOKEN_ID_PLACEHOLDER, token_id);
+
+        // Mention the owner where the placeholder is first used, the post
+        // indexes are expressed in characters.
+        let mentions = text
+            .find(USER_PLACEHOLDER)
+            .map(|index| {
+                let start = text[..index].chars().count() as u64;
+                vec![TextTag {
+                    start: start.into(),
+                    end: (start + owner.chars().count() as u64 - 1).into(),
+                    tag: owner.to_string(),
+                }]
+            })
+            .unwrap_or_default();
+        let text = text.replace(USER_PLACEHOLDER, owner);
+
+        let post = PostsMsg::CreatePost {
+            subspace_id: announce.subspace_id,
+            section_id: announce.section_id,
+            external_id: None,
+            text: Some(text),
+            entities: (!mentions.is_empty()).then_some(Entities {
+                hashtags: vec![],
+                mentions,
+                urls: vec![],
+            }),
+            tags: vec![],
+            attachments: None,
+            author: env.contract.address.clone(),
+            conversation_id: None,
+            reply_settings: ReplySetting::Everyone,
+            referenced_posts: vec![],
+        };
+
+        Ok(vec![CosmosMsg::Custom(DesmosMsg::from(post))])
+    }
+}
@@ -2,6 +2,8 @@
 mod contract_tests;
 pub mod error;
 mod execute;
+pub mod extension;
+mod migrate;
 pub mod msg;
 mod query;
 pub mod state;
@@ -9,7 +11,8 @@ pub mod state;
 use cosmwasm_std::Empty;
 
 pub use crate::error::ContractError;
-pub use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+pub use crate::extension::{DesmosExtension, NoopExtension, PoapExtension};
+pub use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 pub use crate::state::PoapContract;
 
 // This type is re-exported so that contracts interacting with this
@@ -33,11 +36,11 @@ pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub mod entry {
     use super::*;
-    #[cfg(not(feature = "library"))]
+    #[cfg(not(any(feature = "library", feature = "desmos")))]
     use cosmwasm_std::entry_point;
-    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult};
 
-    #[cfg_attr(not(feature = "library"), entry_point)]
+    #[cfg_attr(not(any(feature = "library", feature = "desmos")), entry_point)]
     pub fn instantiate(
         deps: DepsMut,
         env: Env,
@@ -50,7 +53,7 @@ pub mod entry {
         tract.instantiate(deps, env, info, msg)
     }
 
-    #[cfg_attr(not(feature = "library"), entry_point)]
+    #[cfg_attr(not(any(feature = "library", feature = "desmos")), entry_point)]
     pub fn execute(
         deps: DepsMut,
         env: Env,
@@ -61,9 +64,83 @@ pub mod entry {
         tract.execute(deps, env, info, msg)
     }
 
-    #[cfg_attr(not(feature = "library"), entry_point)]
+    #[cfg_attr(not(any(feature = "library", feature = "desmos")), entry_point)]
     pub fn query(deps: Deps, env: Env, msg: QueryMsg<Empty>) -> StdResult<Binary> {
         let tract = PoapContract::<Extension, Empty, Empty, Empty>::default();
         tract.query(deps, env, msg)
     }
+
+    #[cfg_attr(not(any(feature = "library", feature = "desmos")), entry_point)]
+    pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+        let tract = PoapContract::<Extension, Empty, Empty, Empty>::default();
+        tract.migrate(deps, env, msg)
+    }
+
+    #[cfg_attr(not(any(feature = "library", feature = "desmos")), entry_point)]
+    pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+        let tract = PoapContract::<Extension, Empty, Empty, Empty>::default();
+        tract.reply(deps, env, msg)
+    }
+}
+
+/// Entry points that allow the contract to perform the Desmos custom queries and to announce
+/// the mints with Desmos posts, used in place of the ones of the [`entry`] module when the
+/// `desmos` feature is enabled.
+pub mod desmos_entry {
+    use super::*;
+    #[cfg(all(feature = "desmos", not(feature = "library")))]
+    use cosmwasm_std::entry_point;
+    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult};
+    use desmos_bindings::msg::DesmosMsg;
+    use desmos_bindings::query::DesmosQuery;
+
+    #[cfg_attr(all(feature = "desmos", not(feature = "library")), entry_point)]
+    pub fn instantiate(
+        deps: DepsMut<DesmosQuery>,
+        env: Env,
+        info: MessageInfo,
+        msg: InstantiateMsg,
+    ) -> Result<Response<DesmosMsg>, ContractError> {
+        cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+        let tract = PoapContract::<Extension, DesmosMsg, Empty, Empty, DesmosExtension>::default();
+        tract.instantiate(deps.into_empty(), env, info, msg)
+    }
+
+    #[cfg_attr(all(feature = "desmos", not(feature = "library")), entry_point)]
+    pub fn execute(
+        deps: DepsMut<DesmosQuery>,
+        env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg<Extension, Empty>,
+    ) -> Result<Response<DesmosMsg>, ContractError> {
+        let tract = PoapContract::<Extension, DesmosMsg, Empty, Empty, DesmosExtension>::default();
+        tract.execute(deps.into_empty(), env, info, msg)
+    }
+
+    #[cfg_attr(all(feature = "desmos", not(feature = "library")), entry_point)]
+    pub fn query(deps: Deps<DesmosQuery>, env: Env, msg: QueryMsg<Empty>) -> StdResult<Binary> {
+        let tract = PoapContract::<Extension, DesmosMsg, Empty, Empty, DesmosExtension>::default();
+        tract.query(deps.into_empty(), env, msg)
+    }
+
+    #[cfg_attr(all(feature = "desmos", not(feature = "library")), entry_point)]
+    pub fn migrate(
+        deps: DepsMut<DesmosQuery>,
+        env: Env,
+        msg: MigrateMsg,
+    ) -> Result<Response<DesmosMsg>, ContractError> {
+        let tract = PoapContract::<Extension, DesmosMsg, Empty, Empty, DesmosExtension>::default();
+        tract.migrate(deps.into_empty(), env, msg)
+    }
+
+    #[cfg_attr(all(feature = "desmos", not(feature = "library")), entry_point)]
+    pub fn reply(
+        deps: DepsMut<DesmosQuery>,
+        env: Env,
+        msg: Reply,
+    ) -> Result<Response<DesmosMsg>, ContractError> {
+        let tract = PoapContract::<Extension, DesmosMsg, Empty, Empty, DesmosExtension>::default();
+        tract.reply(deps.into_empty(), env, msg)
+    }
 }
@@ -0,0 +1,367 @@
+use crate::error::ContractError;
+use crate::extension::PoapExtension;
+use crate::msg::MigrateMsg;
+use crate::state::{PoapContract, V1Import};
+use crate::{CONTRACT_NAME, CONTRACT_VERSION};
+use cosmwasm_std::{
+    from_slice, to_vec, Addr, CustomMsg, DepsMut, Env, MessageInfo, Order, QuerierWrapper,
+    Response, StdError, StdResult, Storage, Timestamp,
+};
+use cw721::{
+    AllNftInfoResponse, ContractInfoResponse, Cw721QueryMsg, NumTokensResponse, TokensResponse,
+};
+use cw721_base::state::TokenInfo;
+use cw_storage_plus::Item;
+use cw_utils::{Expiration, Scheduled};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+/// Name stored through cw2 by the v1 `poap` contract.
+pub const V1_POAP_CONTRACT_NAME: &str = "crates.io:poap";
+/// Name stored through cw2 by the v1 `cw721-poap` contract.
+pub const V1_CW721_POAP_CONTRACT_NAME: &str = "crates.io:cw721-poap";
+/// Name stored through cw2 by the v1 `poap-manager` contract.
+pub const V1_POAP_MANAGER_CONTRACT_NAME: &str = "crates.io:poap-manager";
+/// Maximum number of POAPs requested to the v1 `cw721-poap` contract in each import,
+/// which is not greater than the page size allowed by its queries.
+const V1_TOKENS_MAX_LIMIT: u32 = 30;
+
+/// Storage layout of the v1 POAP contracts.
+/// Only the fields needed by the migration are declared, the other ones are
+/// ignored while deserializing the stored values.
+pub mod v1 {
+    use cosmwasm_std::{Addr, Timestamp};
+    use serde::{Deserialize, Serialize};
+
+    /// Key of the `Config` item of the `poap` and `poap-manager` contracts.
+    pub const CONFIG_KEY: &str = "config";
+    /// Key of the `EventInfo` item of the `poap` contract.
+    pub const EVENT_INFO_KEY: &str = "event_info";
+    /// Key of the item where the `poap` contract stores the `cw721-poap` contract address.
+    pub const CW721_ADDRESS_KEY: &str = "cw721_address";
+    /// Key of the item where the `cw721-poap` contract stores its minter,
+    /// which is the address of the `poap` contract.
+    pub const CW721_MINTER_KEY: &str = "minter";
+
+    /// Configuration of the v1 `poap` contract.
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct PoapConfig {
+        pub admin: Addr,
+        pub minter: Addr,
+        pub mint_enabled: bool,
+    }
+
+    /// Event info of the v1 `poap` contract.
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct PoapEventInfo {
+        pub creator: Addr,
+        pub start_time: Timestamp,
+        pub end_time: Timestamp,
+        pub per_address_limit: u32,
+        pub poap_uri: String,
+        pub cw721_code_id: u64,
+    }
+
+    /// Configuration of the v1 `poap-manager` contract.
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct PoapManagerConfig {
+        pub admin: Addr,
+        #[serde(alias = "poap_contract_address")]
+        pub poap_address: Addr,
+    }
+}
+
+impl<'a, T, C, E, Q, X> PoapContract<'a, T, C, E, Q, X>
+where
+    T: Serialize + DeserializeOwned + Clone + Debug,
+    C: CustomMsg,
+    E: CustomMsg,
+    Q: CustomMsg,
+    X: PoapExtension<T, C, E, Q>,
+{
+    /// Migrates the contract state to the current version.
+    /// The migration is allowed from a previous version of this contract or from one of the
+    /// v1 `poap`, `cw721-poap` and `poap-manager` contracts.
+    pub fn migrate(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        _msg: MigrateMsg,
+    ) -> Result<Response<C>, ContractError> {
+        let previous = cw2::get_contract_version(deps.storage)?;
+
+        match previous.contract.as_str() {
+            CONTRACT_NAME => {
+                // Ensure that we are not performing a downgrade.
+                cw2::ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+                self.migrate_mint_window(deps.storage)?;
+            }
+            V1_CW721_POAP_CONTRACT_NAME => {
+                // The POAPs are already stored with the cw721-base layout, we only need
+                // to import the event configuration from the v1 poap contract that was
+                // the minter of this one.
+                let poap_address: Addr = Item::new(v1::CW721_MINTER_KEY).load(deps.storage)?;
+                let config = query_v1_item(&deps.querier, &poap_address, v1::CONFIG_KEY)?;
+                let event_info = query_v1_item(&deps.querier, &poap_address, v1::EVENT_INFO_KEY)?;
+                self.save_v1_event(deps.branch(), config, event_info)?;
+            }
+            V1_POAP_CONTRACT_NAME => {
+                let config_item: Item<v1::PoapConfig> = Item::new(v1::CONFIG_KEY);
+                let event_info_item: Item<v1::PoapEventInfo> = Item::new(v1::EVENT_INFO_KEY);
+                let cw721_address_item: Item<Addr> = Item::new(v1::CW721_ADDRESS_KEY);
+
+                let config = config_item.load(deps.storage)?;
+                let event_info = event_info_item.load(deps.storage)?;
+                let cw721_address = cw721_address_item.load(deps.storage)?;
+                config_item.remove(deps.storage);
+                event_info_item.remove(deps.storage);
+                cw721_address_item.remove(deps.storage);
+
+                self.save_v1_contract_info(deps.branch(), &cw721_address)?;
+                self.save_v1_event(deps.branch(), config, event_info)?;
+                self.start_v1_import(deps.branch(), cw721_address)?;
+            }
+            V1_POAP_MANAGER_CONTRACT_NAME => {
+                let config_item: Item<v1::PoapManagerConfig> = Item::new(v1::CONFIG_KEY);
+                let manager_config = config_item.load(deps.storage)?;
+                config_item.remove(deps.storage);
+
+                let poap_address = manager_config.poap_address;
+                let config = query_v1_item(&deps.querier, &poap_address, v1::CONFIG_KEY)?;
+                let event_info = query_v1_item(&deps.querier, &poap_address, v1::EVENT_INFO_KEY)?;
+                let cw721_address =
+                    query_v1_item(&deps.querier, &poap_address, v1::CW721_ADDRESS_KEY)?;
+
+                self.save_v1_contract_info(deps.branch(), &cw721_address)?;
+                self.save_v1_event(deps.branch(), config, event_info)?;
+                self.start_v1_import(deps.branch(), cw721_address)?;
+            }
+            _ => {
+                return Err(ContractError::Version(cw2::VersionError::WrongContract {
+                    expected: CONTRACT_NAME.to_string(),
+                    found: previous.contract,
+                }))
+            }
+        }
+
+        // Contracts that didn't track the POAP serial number used to derive the next id
+        // from the number of minted POAPs, so we start counting from there.
+        if self.next_poap_id.may_load(deps.storage)?.is_none() {
+            let next_poap_id = self.cw721_base.token_count(deps.storage)? + 1;
+            self.next_poap_id.save(deps.storage, &next_poap_id)?;
+        }
+
+        // Contracts that didn't keep the holder index start tracking it from this block.
+        if self.total_supply.may_load(deps.storage)?.is_none() {
+            self.seed_holder_index(deps.storage, env.block.height)?;
+        }
+
+        cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "migrate")
+            .add_attribute("from_contract", previous.contract)
+            .add_attribute("from_version", previous.version)
+            .add_attribute("to_version", CONTRACT_VERSION))
+    }
+
+    /// Converts the v1 event configuration into the POAP contract items.
+    /// * `config` - Configuration of the v1 poap contract.
+    /// * `event_info` - Event info of the v1 poap contract.
+    fn save_v1_event(
+        &self,
+        deps: DepsMut,
+        config: v1::PoapConfig,
+        event_info: v1::PoapEventInfo,
+    ) -> Result<(), ContractError> {
+        if event_info.start_time >= event_info.end_time {
+            return Err(ContractError::InvalidTimestampValues {});
+        }
+
+        cw_ownable::initialize_owner(deps.storage, deps.api, Some(config.admin.as_str()))?;
+        self.metadata_uri.save(deps.storage, &event_info.poap_uri)?;
+        self.minter.save(deps.storage, &Some(config.minter))?;
+        // The v1 POAPs were plain cw721 tokens, so they were always transferable.
+        self.is_transferable.save(deps.storage, &true)?;
+        self.is_mintable.save(deps.storage, &config.mint_enabled)?;
+        self.mint_start_time.save(
+            deps.storage,
+            &Some(Scheduled::AtTime(event_info.start_time)),
+        )?;
+        self.mint_end_time
+            .save(deps.storage, &Some(Expiration::AtTime(event_info.end_time)))?;
+        if event_info.per_address_limit > 0 {
+            self.per_address_limit
+                .save(deps.storage, &event_info.per_address_limit)?;
+        }
+
+        Ok(())
+    }
+
+    /// Converts the mint start and end time stored as timestamps by the previous versions
+    /// of this contract into the [`Scheduled`] and [`Expiration`] representations.
+    fn migrate_mint_window(&self, storage: &mut dyn Storage) -> StdResult<()> {
+        let start_key = self.mint_start_time.as_slice();
+        if let Some(Ok(start_time)) = storage
+            .get(start_key)
+            .map(|data| from_slice::<Option<Timestamp>>(&data))
+        {
+            storage.set(start_key, &to_vec(&start_time.map(Scheduled::AtTime))?);
+        }
+
+        let end_key = self.mint_end_time.as_slice();
+        if let Some(Ok(end_time)) = storage
+            .get(end_key)
+            .map(|data| from_slice::<Option<Timestamp>>(&data))
+        {
+            storage.set(end_key, &to_vec(&end_time.map(Expiration::AtTime))?);
+        }
+
+        Ok(())
+    }
+
+    /// Builds the holder index from the POAPs that exist at the time of the migration.
+    /// * `height` - Height of the block in which the migration is executed.
+    fn seed_holder_index(&self, storage: &mut dyn Storage, height: u64) -> StdResult<()> {
+        let mut balances: BTreeMap<Addr, u64> = BTreeMap::new();
+        for item in self
+            .cw721_base
+            .tokens
+            .range(storage, None, None, Order::Ascending)
+        {
+            let (_, token) = item?;
+            *balances.entry(token.owner).or_default() += 1;
+        }
+
+        let total_supply = balances.values().sum();
+        for (owner, balance) in balances {
+            self.holder_balances
+                .save(storage, &owner, &balance, height)?;
+        }
+        self.total_supply.save(storage, &total_supply, height)
+    }
+
+    /// Prepares the import of the POAPs minted by the v1 `cw721-poap` contract, that are
+    /// copied into this contract with the [`ExecuteMsg::ImportV1Tokens`] message since
+    /// importing all of them during the migration could exceed the gas limit.
+    /// The ids of the v1 POAPs are reserved right away, so that they are not reused by the
+    /// POAPs minted before the import is completed.
+    /// * `cw721_address` - Address of the v1 cw721-poap contract.
+    ///
+    /// [`ExecuteMsg::ImportV1Tokens`]: crate::msg::ExecuteMsg::ImportV1Tokens
+    fn start_v1_import(&self, deps: DepsMut, cw721_address: Addr) -> StdResult<()> {
+        let NumTokensResponse { count } = deps
+            .querier
+            .query_wasm_smart(&cw721_address, &Cw721QueryMsg::NumTokens {})?;
+        self.next_poap_id.save(deps.storage, &(count + 1))?;
+        self.v1_import.save(
+            deps.storage,
+            &V1Import {
+                cw721_address,
+                start_after: None,
+            },
+        )
+    }
+
+    /// Copies the next POAPs minted by the v1 `cw721-poap` contract into this contract,
+    /// so that their holders are still taken into account by the per address limit,
+    /// this action can be executed only from the contract admin.
+    /// The approvals are not copied since they were granted on the v1 contract.
+    /// The v1 contract must not allow the transfers anymore, otherwise the imported
+    /// POAPs could still be moved in there.
+    /// * `limit` - Maximum number of POAPs to import, capped to 30.
+    pub fn import_v1_tokens(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        limit: u32,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_is_admin(deps.storage, &info.sender)?;
+        if limit == 0 {
+            return Err(ContractError::InvalidImportLimit {});
+        }
+        let limit = limit.min(V1_TOKENS_MAX_LIMIT);
+        let mut import = self
+            .v1_import
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoV1TokensToImport {})?;
+
+        let TokensResponse { tokens } = deps.querier.query_wasm_smart(
+            &import.cw721_address,
+            &Cw721QueryMsg::AllTokens {
+                start_after: import.start_after.clone(),
+                limit: Some(limit),
+            },
+        )?;
+        for token_id in tokens.iter() {
+            let nft: AllNftInfoResponse<T> = deps.querier.query_wasm_smart(
+                &import.cw721_address,
+                &Cw721QueryMsg::AllNftInfo {
+                    token_id: token_id.clone(),
+                    include_expired: Some(true),
+                },
+            )?;
+            let token = TokenInfo {
+                owner: deps.api.addr_validate(&nft.access.owner)?,
+                approvals: vec![],
+                token_uri: nft.info.token_uri,
+                extension: nft.info.extension,
+            };
+            self.cw721_base
+                .tokens
+                .save(deps.storage, token_id, &token)?;
+            self.cw721_base.increment_tokens(deps.storage)?;
+            self.update_holder_index(deps.storage, env.block.height, None, Some(&token.owner))?;
+        }
+
+        // The v1 contract returns less POAPs than requested only once all of them have been listed.
+        let is_completed = tokens.len() < limit as usize;
+        if is_completed {
+            self.v1_import.remove(deps.storage);
+        } else {
+            import.start_after = tokens.last().cloned();
+            self.v1_import.save(deps.storage, &import)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "import_v1_tokens")
+            .add_attribute("sender", info.sender)
+            .add_attribute("imported", tokens.len().to_string())
+            .add_attribute("is_completed", is_completed.to_string()))
+    }
+
+    /// Copies the name and symbol of the v1 `cw721-poap` contract into this contract.
+    /// * `cw721_address` - Address of the v1 cw721-poap contract.
+    fn save_v1_contract_info(&self, deps: DepsMut, cw721_address: &Addr) -> StdResult<()> {
+        let contract_info: ContractInfoResponse = query_v1_item(
+            &deps.querier,
+            cw721_address,
+            self.cw721_base.contract_info.as_slice(),
+        )?;
+        self.cw721_base
+            .contract_info
+            .save(deps.storage, &contract_info)
+    }
+}
+
+/// Reads an item from the storage of a v1 contract with a raw query.
+/// * `contract` - Address of the contract that stores the item.
+/// * `key` - Key of the item.
+fn query_v1_item<T: DeserializeOwned>(
+    querier: &QuerierWrapper,
+    contract: &Addr,
+    key: impl AsRef<[u8]>,
+) -> StdResult<T> {
+    let key = key.as_ref();
+    let data = querier.query_wasm_raw(contract, key)?.ok_or_else(|| {
+        StdError::not_found(format!(
+            "{} in contract {}",
+            String::from_utf8_lossy(key),
+            contract
+        ))
+    })?;
+    from_slice(&data)
+}
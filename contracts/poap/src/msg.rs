@@ -1,10 +1,12 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Binary, Timestamp};
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Coin, CosmosMsg, StdResult, Timestamp, Uint64, WasmMsg,
+};
 use cw721_base::{ExecuteMsg as Cw721BaseExecuteMsg, QueryMsg as Cw721BaseQueryMsg};
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
-use cw_utils::Expiration;
+use cw_utils::{Expiration, Scheduled};
 use schemars::JsonSchema;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -14,6 +16,7 @@ pub struct InstantiateMsg {
     pub symbol: String,
     /// The URI where users can view the associated metadata for the POAPs,
     /// ideally following the ERC-721 metadata scheme in a JSON file.
+    /// The `{token_id}` placeholder is replaced with the id of each minted POAP.
     pub metadata_uri: String,
     /// Who controls the contract.
     /// If None will be used the address of who is instantiating the contract.
@@ -24,12 +27,213 @@ pub struct InstantiateMsg {
     pub is_transferable: bool,
     /// Indicates whether users can mint the POAPs.
     pub is_mintable: bool,
-    /// Identifies the timestamp at which the minting of the POAP will be enabled.
+    /// Identifies the block height or timestamp at which the minting of the POAP will be enabled.
     /// If not set, the minting is always enabled.
-    pub mint_start_time: Option<Timestamp>,
-    /// Identifies the timestamp at which the minting of the POAP will be disabled.
+    pub mint_start_time: Option<Scheduled>,
+    /// Identifies the block height or timestamp at which the minting of the POAP will be disabled.
     /// If not set, the minting will never end.
-    pub mint_end_time: Option<Timestamp>,
+    pub mint_end_time: Option<Expiration>,
+    /// Maximum number of POAPs that can be minted, including the ones that are burned later.
+    /// If not set, there is no limit.
+    pub max_supply: Option<u64>,
+    /// Maximum number of POAPs that an address can own.
+    /// If not set, each address can own only one POAP.
+    pub per_address_limit: Option<u32>,
+    /// Price that the users pay to mint a POAP with the `Mint` message.
+    /// If not set, the minting is free.
+    pub mint_price: Option<Vec<Coin>>,
+    /// Policy that restricts the transfers of the POAPs.
+    /// If not set, the transferable POAPs can be freely transferred.
+    pub transfer_policy: Option<TransferPolicy>,
+    /// Optional address that is allowed to pause the contract, but not to unpause it.
+    pub pauser: Option<String>,
+    /// Configuration of the Desmos posts that announce the minted POAPs.
+    /// The posts are created only by the Desmos entry points.
+    pub announce: Option<AnnounceConfig>,
+}
+
+/// Message used to migrate the contract from a previous version or from
+/// one of the v1 `poap`, `cw721-poap` and `poap-manager` contracts.
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// Voucher issued off-chain that allows to mint a POAP for the recipient.
+#[cw_serde]
+pub struct MintVoucher {
+    /// Address of the user for whom the POAP will be minted.
+    pub recipient: String,
+    /// Id of the event for which the POAP will be minted.
+    /// If None, the POAP is minted for the contract default event.
+    pub event_id: Option<u64>,
+    /// Timestamp after which the voucher can't be used anymore.
+    pub expires_at: Timestamp,
+    /// Unique number that prevents the voucher from being used twice.
+    pub nonce: u64,
+}
+
+/// Document signed by the voucher signer, its SHA-256 hash is the message
+/// that is checked against the voucher signature.
+#[cw_serde]
+pub struct VoucherSignDoc {
+    /// Address of the POAP contract where the voucher can be used.
+    pub contract_address: String,
+    pub recipient: String,
+    pub event_id: Option<u64>,
+    pub expires_at: Timestamp,
+    pub nonce: u64,
+}
+
+/// Rule that a user must satisfy to mint a POAP.
+/// The rules are checked through the Desmos custom queries, so they can be
+/// used only when the contract is deployed on a Desmos chain.
+#[cw_serde]
+pub enum EligibilityRule {
+    /// The user must have a Desmos profile.
+    HasProfile {},
+    /// The user must have a verified link to the given application.
+    HasAppLink { application: String },
+    /// The user must be a member of the given subspace, meaning that it has been granted the
+    /// given permission inside the subspace root section, directly or through a user group.
+    SubspaceMember {
+        subspace_id: Uint64,
+        permission: String,
+    },
+    /// The user must be a member of the given user group.
+    UserGroupMember { subspace_id: Uint64, group_id: u32 },
+}
+
+/// Policy that restricts the transfers of the transferable POAPs.
+/// The POAPs reassigned with `Recover` are not subject to the policy.
+#[cw_serde]
+pub enum TransferPolicy {
+    /// The POAPs can be transferred only until the deadline.
+    Deadline { deadline: Expiration },
+    /// Each POAP can be transferred only once, so that the user that has minted it can gift it.
+    SingleTransfer {},
+    /// The POAPs can be transferred only to users that don't own any POAP.
+    NonHoldersOnly {},
+    /// The POAPs can be transferred only to the allowlisted contracts,
+    /// which are also the only ones that can be approved to transfer them.
+    AllowlistedContracts { contracts: Vec<String> },
+}
+
+/// Configuration of the Desmos posts that announce the minted POAPs.
+#[cw_serde]
+pub struct AnnounceConfig {
+    /// Id of the subspace where the posts are created.
+    pub subspace_id: Uint64,
+    /// Id of the subspace section where the posts are created.
+    pub section_id: u32,
+    /// Template of the posts text.
+    /// The `{user}` placeholder is replaced with the address of the user that received the POAP,
+    /// who is also mentioned in the post, and `{token_id}` with the id of the POAP.
+    pub text: String,
+}
+
+impl fmt::Display for TransferPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransferPolicy::Deadline { .. } => write!(f, "deadline"),
+            TransferPolicy::SingleTransfer {} => write!(f, "single_transfer"),
+            TransferPolicy::NonHoldersOnly {} => write!(f, "non_holders_only"),
+            TransferPolicy::AllowlistedContracts { .. } => write!(f, "allowlisted_contracts"),
+        }
+    }
+}
+
+/// Configuration of an event hosted by the contract.
+#[cw_serde]
+pub struct EventInfo {
+    /// The URI where users can view the associated metadata for the event POAPs,
+    /// ideally following the ERC-721 metadata scheme in a JSON file.
+    /// The `{token_id}` placeholder is replaced with the id of each minted POAP.
+    pub metadata_uri: String,
+    /// Additional address that is allowed to mint the event POAPs on behalf of other users.
+    pub minter: Option<Addr>,
+    /// Specifies whether each event POAP can be transferred from one user to another.
+    pub is_transferable: bool,
+    /// Indicates whether users can mint the event POAPs.
+    pub is_mintable: bool,
+    /// Identifies the block height or timestamp at which the minting of the event POAPs will be enabled.
+    /// If not set, the minting is always enabled.
+    pub mint_start_time: Option<Scheduled>,
+    /// Identifies the block height or timestamp at which the minting of the event POAPs will be disabled.
+    /// If not set, the minting will never end.
+    pub mint_end_time: Option<Expiration>,
+}
+
+/// Information about the mint of a POAP.
+#[cw_serde]
+pub struct MintInfo {
+    /// Height of the block in which the POAP has been minted.
+    pub minted_at_height: u64,
+    /// Time of the block in which the POAP has been minted.
+    pub minted_at_time: Timestamp,
+    /// Address that has sent the message that minted the POAP.
+    pub minted_by: Addr,
+}
+
+/// Information about the revocation of a POAP.
+#[cw_serde]
+pub struct RevocationInfo {
+    /// Address that owned the POAP when it has been revoked.
+    pub owner: Addr,
+    /// Why the POAP has been revoked.
+    pub reason: String,
+    /// Address that has revoked the POAP.
+    pub revoked_by: Addr,
+}
+
+/// Notification sent to the hook contracts when a POAP is minted, burned or transferred.
+#[cw_serde]
+pub enum PoapHookMsg {
+    /// A POAP has been minted.
+    Minted {
+        token_id: String,
+        owner: String,
+        /// Id of the event of the POAP, None for the contract default event.
+        event_id: Option<u64>,
+    },
+    /// A POAP has been burned.
+    Burned {
+        token_id: String,
+        /// Owner of the POAP before it was burned.
+        owner: String,
+        /// Id of the event of the POAP, None for the contract default event.
+        event_id: Option<u64>,
+    },
+    /// A POAP has been transferred to a new owner.
+    Transferred {
+        token_id: String,
+        previous_owner: String,
+        owner: String,
+        /// Id of the event of the POAP, None for the contract default event.
+        event_id: Option<u64>,
+    },
+}
+
+impl PoapHookMsg {
+    /// Serializes the notification as the `poap_hook` message of the hook contract.
+    pub fn into_binary(self) -> StdResult<Binary> {
+        to_binary(&PoapHookExecuteMsg::PoapHook(self))
+    }
+
+    /// Creates the message that sends the notification to a hook contract.
+    /// * `contract_addr` - Address of the hook contract.
+    pub fn into_cosmos_msg<C>(self, contract_addr: impl Into<String>) -> StdResult<CosmosMsg<C>> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg: self.into_binary()?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+/// Message that the hook contracts must handle to receive the notifications.
+#[cw_serde]
+enum PoapHookExecuteMsg {
+    PoapHook(PoapHookMsg),
 }
 
 /// This is like Cw721ExecuteMsg but we add a Mint command for an owner
@@ -65,10 +269,26 @@ pub enum ExecuteMsg<T, E> {
     /// Remove previously granted ApproveAll permission.
     RevokeAll { operator: String },
     /// Mint a new POAP for the caller.
-    Mint { extension: T },
+    Mint {
+        /// Id of the event for which the POAP will be minted.
+        /// If None, the POAP is minted for the contract default event.
+        event_id: Option<u64>,
+        extension: T,
+        /// Address of the user that referred the caller, must own a POAP of this contract.
+        referrer: Option<String>,
+    },
     /// Mint a new POAP for the provided users, can
-    /// only be called from the contract minter.
-    MintTo { users: Vec<String>, extension: T },
+    /// only be called from the event minter.
+    MintTo {
+        /// Id of the event for which the POAPs will be minted.
+        /// If None, the POAPs are minted for the contract default event.
+        event_id: Option<u64>,
+        users: Vec<String>,
+        extension: T,
+        /// If true, the users that have an invalid address or already own the POAP
+        /// are skipped instead of failing the whole batch.
+        skip_invalid: Option<bool>,
+    },
     /// Burn a POAP the sender has access to.
     Burn { token_id: String },
     /// Allow to update the user with the mint permissions,
@@ -80,15 +300,218 @@ pub enum ExecuteMsg<T, E> {
     /// Sets if the users can transfer their POAP,
     /// can only be called from the contract admin.
     SetTransferable { transferable: bool },
+    /// Sets the policy that restricts the transfers of the POAPs,
+    /// can only be called from the contract admin.
+    /// If None the transferable POAPs can be freely transferred.
+    SetTransferPolicy { policy: Option<TransferPolicy> },
     /// Sets the time period of when the POAP can be minted from
     /// the users, can only be called from the contract admin.
     SetMintStartEndTime {
-        /// Identifies the timestamp at which the minting of the POAP will be enabled.
+        /// Identifies the block height or timestamp at which the minting of the POAP will be enabled.
         /// If None, the minting is always enabled.
-        start_time: Option<Timestamp>,
-        /// Identifies the timestamp at which the minting of the POAP will be disabled.
+        start_time: Option<Scheduled>,
+        /// Identifies the block height or timestamp at which the minting of the POAP will be disabled.
         /// If None, the minting will never end.
-        end_time: Option<Timestamp>,
+        end_time: Option<Expiration>,
+    },
+    /// Registers a batch of claim codes that can be used to mint a POAP,
+    /// can only be called from the contract admin.
+    AddClaimCodes {
+        /// SHA-256 hashes of the claim codes.
+        code_hashes: Vec<Binary>,
+    },
+    /// Removes a batch of claim codes that have not been used yet,
+    /// can only be called from the contract admin.
+    RevokeClaimCodes {
+        /// SHA-256 hashes of the claim codes.
+        code_hashes: Vec<Binary>,
+    },
+    /// Mint a new POAP for the caller using one of the registered claim codes.
+    /// Each claim code can be used only once.
+    MintWithCode {
+        /// Id of the event for which the POAP will be minted.
+        /// If None, the POAP is minted for the contract default event.
+        event_id: Option<u64>,
+        code: String,
+        extension: T,
+    },
+    /// Sets the root of the Merkle tree built from the addresses that are allowed
+    /// to mint a POAP with [`ExecuteMsg::MintWithProof`],
+    /// can only be called from the contract admin.
+    SetAllowlist {
+        /// SHA-256 Merkle root, if None the allowlist minting is disabled.
+        merkle_root: Option<Binary>,
+    },
+    /// Mint a new POAP for the caller proving that its address is part of the allowlist.
+    MintWithProof {
+        /// Id of the event for which the POAP will be minted.
+        /// If None, the POAP is minted for the contract default event.
+        event_id: Option<u64>,
+        /// SHA-256 hashes of the sibling nodes from the caller leaf up to the Merkle root.
+        proof: Vec<Binary>,
+        extension: T,
+    },
+    /// Updates the secp256k1 public key that signs the mint vouchers,
+    /// can only be called from the contract admin.
+    UpdateVoucherSigner {
+        /// Compressed or uncompressed secp256k1 public key,
+        /// if None the voucher minting is disabled.
+        public_key: Option<Binary>,
+    },
+    /// Mint a new POAP for the voucher recipient.
+    MintWithVoucher {
+        voucher: MintVoucher,
+        /// secp256k1 signature of the voucher [`VoucherSignDoc`] in the 64 bytes
+        /// compact format.
+        signature: Binary,
+        extension: T,
+    },
+    /// Sets the rules that the users must satisfy to mint a POAP,
+    /// can only be called from the contract admin.
+    SetEligibilityRules { rules: Vec<EligibilityRule> },
+    /// Sets the maximum number of POAPs that can be minted,
+    /// can only be called from the contract admin.
+    SetMaxSupply {
+        /// The new max supply, if None there is no limit.
+        max_supply: Option<u64>,
+    },
+    /// Sets the maximum number of POAPs that an address can own,
+    /// can only be called from the contract admin.
+    SetPerAddressLimit { limit: u32 },
+    /// Creates a new event whose POAPs are minted from this contract,
+    /// can only be called from the contract admin.
+    CreateEvent {
+        /// The URI where users can view the associated metadata for the event POAPs.
+        metadata_uri: String,
+        /// Additional address that is allowed to mint the event POAPs on behalf of other users.
+        minter: Option<String>,
+        /// Specifies whether each event POAP can be transferred from one user to another.
+        is_transferable: bool,
+        /// Indicates whether users can mint the event POAPs.
+        is_mintable: bool,
+        /// Identifies the block height or timestamp at which the minting of the event POAPs will be enabled.
+        /// If None, the minting is always enabled.
+        mint_start_time: Option<Scheduled>,
+        /// Identifies the block height or timestamp at which the minting of the event POAPs will be disabled.
+        /// If None, the minting will never end.
+        mint_end_time: Option<Expiration>,
+    },
+    /// Updates the configuration of an existing event,
+    /// can only be called from the contract admin.
+    UpdateEvent {
+        /// Id of the event to update.
+        event_id: u64,
+        /// The URI where users can view the associated metadata for the event POAPs.
+        metadata_uri: String,
+        /// Additional address that is allowed to mint the event POAPs on behalf of other users.
+        minter: Option<String>,
+        /// Specifies whether each event POAP can be transferred from one user to another.
+        is_transferable: bool,
+        /// Indicates whether users can mint the event POAPs.
+        is_mintable: bool,
+        /// Identifies the block height or timestamp at which the minting of the event POAPs will be enabled.
+        /// If None, the minting is always enabled.
+        mint_start_time: Option<Scheduled>,
+        /// Identifies the block height or timestamp at which the minting of the event POAPs will be disabled.
+        /// If None, the minting will never end.
+        mint_end_time: Option<Expiration>,
+    },
+    /// Updates the metadata URI of the contract default event,
+    /// can only be called from the contract admin.
+    UpdateMetadataUri {
+        /// The new metadata URI, the `{token_id}` placeholder is replaced
+        /// with the id of each POAP.
+        metadata_uri: String,
+        /// Ids of already minted POAPs whose `token_uri` will be recomputed from
+        /// the metadata URI of their event.
+        refresh_token_ids: Option<Vec<String>>,
+    },
+    /// Permanently prevents the metadata URIs from being updated,
+    /// can only be called from the contract admin.
+    FreezeMetadata {},
+    /// Creates an airdrop job that mints the POAPs to a list of recipients across
+    /// multiple transactions, can only be called from the contract admin or the event minter.
+    CreateAirdrop {
+        /// Id of the event for which the POAPs will be minted.
+        /// If None, the POAPs are minted for the contract default event.
+        event_id: Option<u64>,
+        /// First chunk of recipients, more can be added with [`ExecuteMsg::AddAirdropRecipients`].
+        recipients: Vec<String>,
+        extension: T,
+    },
+    /// Appends a chunk of recipients to an airdrop job,
+    /// can only be called from the contract admin or the job creator.
+    AddAirdropRecipients {
+        job_id: u64,
+        recipients: Vec<String>,
+    },
+    /// Mints the POAPs to the next recipients of an airdrop job, can be called from anyone.
+    /// The recipients that have an invalid address or already own the POAP are skipped,
+    /// while the job is stopped once the max supply is reached.
+    ProcessAirdrop {
+        job_id: u64,
+        /// Maximum number of recipients to process, must be greater than zero.
+        limit: u32,
+    },
+    /// Cancels an airdrop job so that its remaining recipients are not processed,
+    /// can only be called from the contract admin or the event minter.
+    CancelAirdrop { job_id: u64 },
+    /// Reassigns a POAP to another user, can only be called from the contract admin
+    /// or the event minter.
+    Recover { token_id: String, new_owner: String },
+    /// Burns a POAP recording the reason of its revocation, can only be called from
+    /// the contract admin or the event minter.
+    /// This is different from [`ExecuteMsg::Revoke`], that removes an approval.
+    RevokePoap { token_id: String, reason: String },
+    /// Registers an additional minter or updates an already registered one,
+    /// can only be called from the contract admin.
+    AddMinter {
+        minter: String,
+        /// Maximum number of POAPs that the minter can mint, if None there is no limit.
+        quota: Option<u64>,
+        /// When the minter loses the minting permissions, if None they never expire.
+        expiration: Option<Expiration>,
+    },
+    /// Removes a registered minter, can only be called from the contract admin.
+    RemoveMinter { minter: String },
+    /// Sets the price that the users pay to mint a POAP with the `Mint` message,
+    /// can only be called from the contract admin.
+    /// An empty list makes the minting free.
+    SetMintPrice { mint_price: Vec<Coin> },
+    /// Withdraws the fees paid to mint the POAPs, can only be called from the contract admin.
+    WithdrawFees {
+        receiver: String,
+        /// Fees to withdraw, if None all the collected fees are withdrawn.
+        amount: Option<Vec<Coin>>,
+    },
+    /// Registers a contract that is notified with a [`PoapHookMsg`] each time a POAP is
+    /// minted, burned or transferred, can only be called from the contract admin.
+    AddHook {
+        contract: String,
+        /// If true a failure of the contract is ignored,
+        /// otherwise it reverts the action that has notified the contract.
+        ignore_errors: bool,
+    },
+    /// Removes a registered hook contract, can only be called from the contract admin.
+    RemoveHook { contract: String },
+    /// Pauses all the operations that change the contract state, except the ones
+    /// that manage the contract ownership and the pause itself,
+    /// can only be called from the contract admin or the pauser.
+    Pause {},
+    /// Resumes the operations stopped with `Pause`, can only be called from the contract admin.
+    Unpause {},
+    /// Updates the address that is allowed to pause the contract,
+    /// can only be called from the contract admin.
+    UpdatePauser { pauser: Option<String> },
+    /// Sets the configuration of the Desmos posts that announce the minted POAPs,
+    /// can only be called from the contract admin.
+    /// If None the mints are not announced.
+    SetAnnounce { announce: Option<AnnounceConfig> },
+    /// Imports the next POAPs minted with the v1 `cw721-poap` contract this contract
+    /// has been migrated from, can only be called from the contract admin.
+    ImportV1Tokens {
+        /// Maximum number of POAPs to import, must be greater than zero.
+        limit: u32,
     },
     /// Extension msg.
     Extension { msg: E },
@@ -179,14 +602,154 @@ pub enum QueryMsg<Q: JsonSchema> {
     /// Returns if the POAP is transferable.
     #[returns(IsTransferableResponse)]
     IsTransferable {},
+    /// Returns the policy that restricts the transfers of the POAPs.
+    #[returns(TransferPolicyResponse)]
+    TransferPolicy {},
+    /// Returns if the contract is paused.
+    #[returns(IsPausedResponse)]
+    IsPaused {},
+    /// Returns the configuration of the Desmos posts that announce the minted POAPs.
+    #[returns(AnnounceResponse)]
+    Announce {},
     /// Return the mint start and end time.
     #[returns(MintStartEndTimeResponse)]
     MintStartEndTime {},
-    /// Extension query.
-    #[returns(())]
+    /// Returns the number of claim codes that have not been used yet.
+    #[returns(ClaimCodesCountResponse)]
+    ClaimCodesCount {},
+    /// Returns the Merkle root of the allowlist.
+    #[returns(AllowlistResponse)]
+    Allowlist {},
+    /// Returns the public key that signs the mint vouchers.
+    #[returns(VoucherSignerResponse)]
+    VoucherSigner {},
+    /// Returns the rules that the users must satisfy to mint a POAP.
+    #[returns(EligibilityRulesResponse)]
+    EligibilityRules {},
+    /// Returns the number of POAPs that can still be minted.
+    #[returns(RemainingSupplyResponse)]
+    RemainingSupply {},
+    /// Returns the configuration of an event.
+    #[returns(EventResponse)]
+    Event { event_id: u64 },
+    /// Lists the events hosted by the contract.
+    #[returns(EventsResponse)]
+    Events {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the metadata URI of the contract default event.
+    #[returns(MetadataUriResponse)]
+    MetadataUri {},
+    /// Returns the progress of an airdrop job.
+    #[returns(AirdropJobResponse)]
+    AirdropJob { job_id: u64 },
+    /// Lists the recipients of an airdrop job that have been skipped.
+    #[returns(AirdropFailuresResponse)]
+    AirdropFailures {
+        job_id: u64,
+        /// Position in the recipients list after which the listing starts.
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Lists the ids of the POAPs minted for an event.
+    #[returns(cw721::TokensResponse)]
+    EventTokens {
+        event_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the holders of the POAPs together with the information about their mint.
+    #[returns(HoldersResponse)]
+    Holders {
+        /// Id of the POAP after which the listing starts.
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Gets the POAPs owned by a user together with the information about their mint.
+    #[returns(PoapOfResponse)]
+    PoapOf {
+        user: String,
+        /// Id of the POAP after which the listing starts.
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Gets why a POAP has been revoked, error if the POAP has not been revoked.
+    #[returns(RevocationResponse)]
+    Revocation { token_id: String },
+    /// Lists the minters registered by the admin.
+    #[returns(MintersResponse)]
+    Minters {
+        /// Address of the minter after which the listing starts.
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the price that the users pay to mint a POAP.
+    #[returns(MintPriceResponse)]
+    MintPrice {},
+    /// Returns the fees paid to mint the POAPs that have not been withdrawn yet.
+    #[returns(CollectedFeesResponse)]
+    CollectedFees {},
+    /// Returns the contracts that are notified when a POAP is minted, burned or transferred.
+    #[returns(HooksResponse)]
+    Hooks {},
+    /// Returns the whole configuration of the contract and of its default event,
+    /// together with the current mint status.
+    #[returns(ConfigResponse)]
+    Config {},
+    /// Returns the number of POAPs owned by a user at the beginning of a block.
+    #[returns(HolderAtHeightResponse)]
+    HolderAtHeight {
+        user: String,
+        /// If not set, the current number of POAPs is returned.
+        height: Option<u64>,
+    },
+    /// Returns the number of existing POAPs at the beginning of a block.
+    #[returns(TotalSupplyAtHeightResponse)]
+    TotalSupplyAtHeight {
+        /// If not set, the current number of POAPs is returned.
+        height: Option<u64>,
+    },
+    /// Lists the referrers ordered by their number of referrals, starting from the highest.
+    #[returns(TopReferrersResponse)]
+    TopReferrers {
+        /// Address of the referrer after which the listing starts.
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the number of users that minted their POAP with a referrer.
+    #[returns(ReferralsResponse)]
+    Referrals { referrer: String },
+    /// Extension query, the response is defined by the [`crate::PoapExtension`]
+    /// used by the contract.
+    #[returns(Binary)]
     Extension { msg: Q },
 }
 
+/// Data of the response to [`ExecuteMsg::MintTo`].
+#[cw_serde]
+pub struct MintToResponse {
+    /// Outcome of the mint for each of the provided users, in the same order.
+    pub results: Vec<MintToResult>,
+}
+
+/// Outcome of [`ExecuteMsg::MintTo`] for a single user.
+#[cw_serde]
+pub struct MintToResult {
+    pub user: String,
+    pub status: MintToStatus,
+}
+
+#[cw_serde]
+pub enum MintToStatus {
+    /// The POAP has been minted.
+    Minted { token_id: String },
+    /// The user already owns the maximum number of POAPs allowed per address.
+    AlreadyOwned {},
+    /// The user address is not valid.
+    InvalidAddress {},
+}
+
 /// Response to [`QueryMsg::IsMintable`].
 #[cw_serde]
 pub struct IsMintableResponse {
@@ -202,8 +765,97 @@ pub struct IsTransferableResponse {
 /// Response to [`QueryMsg::MintStartEndTime`].
 #[cw_serde]
 pub struct MintStartEndTimeResponse {
-    pub start_time: Option<Timestamp>,
-    pub end_time: Option<Timestamp>,
+    pub start_time: Option<Scheduled>,
+    pub end_time: Option<Expiration>,
+}
+
+/// Response to [`QueryMsg::ClaimCodesCount`].
+#[cw_serde]
+pub struct ClaimCodesCountResponse {
+    pub count: u64,
+}
+
+/// Response to [`QueryMsg::Allowlist`].
+#[cw_serde]
+pub struct AllowlistResponse {
+    pub merkle_root: Option<Binary>,
+}
+
+/// Response to [`QueryMsg::VoucherSigner`].
+#[cw_serde]
+pub struct VoucherSignerResponse {
+    pub public_key: Option<Binary>,
+}
+
+/// Response to [`QueryMsg::EligibilityRules`].
+#[cw_serde]
+pub struct EligibilityRulesResponse {
+    pub rules: Vec<EligibilityRule>,
+}
+
+/// Response to [`QueryMsg::RemainingSupply`].
+#[cw_serde]
+pub struct RemainingSupplyResponse {
+    /// Maximum number of POAPs that can be minted, None if there is no limit.
+    pub max_supply: Option<u64>,
+    /// Number of POAPs minted so far, including the burned ones.
+    pub supply: u64,
+    /// Number of POAPs that can still be minted, None if there is no limit.
+    pub remaining: Option<u64>,
+}
+
+/// Response to [`QueryMsg::MetadataUri`].
+#[cw_serde]
+pub struct MetadataUriResponse {
+    pub metadata_uri: String,
+    /// true if the metadata URIs can't be updated anymore.
+    pub is_frozen: bool,
+}
+
+/// Response to [`QueryMsg::AirdropJob`].
+#[cw_serde]
+pub struct AirdropJobResponse {
+    pub job_id: u64,
+    pub event_id: Option<u64>,
+    /// Number of recipients uploaded so far.
+    pub total_recipients: u64,
+    /// Number of recipients that have been processed.
+    pub processed: u64,
+    /// Number of POAPs that have been minted.
+    pub minted: u64,
+    /// Number of recipients that have been skipped.
+    pub failed: u64,
+    pub is_cancelled: bool,
+    /// Error that has stopped the job, None if the job can still be processed.
+    pub error: Option<String>,
+}
+
+/// Response to [`QueryMsg::AirdropFailures`].
+#[cw_serde]
+pub struct AirdropFailuresResponse {
+    pub failures: Vec<AirdropFailure>,
+}
+
+/// Recipient of an airdrop job that has been skipped.
+#[cw_serde]
+pub struct AirdropFailure {
+    /// Position of the recipient in the recipients list.
+    pub index: u64,
+    pub user: String,
+    pub status: MintToStatus,
+}
+
+/// Response to [`QueryMsg::Event`].
+#[cw_serde]
+pub struct EventResponse {
+    pub event_id: u64,
+    pub info: EventInfo,
+}
+
+/// Response to [`QueryMsg::Events`].
+#[cw_serde]
+pub struct EventsResponse {
+    pub events: Vec<EventResponse>,
 }
 
 impl<T, E> From<ExecuteMsg<T, E>> for Cw721BaseExecuteMsg<T, E>
@@ -332,3 +984,173 @@ where
         }
     }
 }
+
+/// Response to [`QueryMsg::Holders`].
+#[cw_serde]
+pub struct HoldersResponse {
+    pub holders: Vec<HolderResponse>,
+}
+
+/// POAP owned by a holder.
+#[cw_serde]
+pub struct HolderResponse {
+    pub owner: String,
+    pub token_id: String,
+    /// Information about the mint of the POAP,
+    /// None if the POAP has been minted before this information was tracked.
+    pub mint_info: Option<MintInfo>,
+}
+
+/// Response to [`QueryMsg::PoapOf`].
+#[cw_serde]
+pub struct PoapOfResponse {
+    pub poaps: Vec<HolderResponse>,
+}
+
+/// Response to [`QueryMsg::Revocation`].
+#[cw_serde]
+pub struct RevocationResponse {
+    pub token_id: String,
+    pub info: RevocationInfo,
+}
+
+/// Response to [`QueryMsg::Minters`].
+#[cw_serde]
+pub struct MintersResponse {
+    pub minters: Vec<RegisteredMinter>,
+}
+
+/// Minter registered by the admin.
+#[cw_serde]
+pub struct RegisteredMinter {
+    pub minter: String,
+    /// Maximum number of POAPs that the minter can mint, None if there is no limit.
+    pub quota: Option<u64>,
+    /// Number of POAPs minted so far.
+    pub minted: u64,
+    /// Number of POAPs that the minter can still mint, None if there is no limit.
+    pub remaining_quota: Option<u64>,
+    /// When the minter loses the minting permissions, None if they never expire.
+    pub expiration: Option<Expiration>,
+}
+
+/// Response to [`QueryMsg::MintPrice`].
+#[cw_serde]
+pub struct MintPriceResponse {
+    /// Price that the users pay to mint a POAP, empty if the minting is free.
+    pub mint_price: Vec<Coin>,
+}
+
+/// Response to [`QueryMsg::CollectedFees`].
+#[cw_serde]
+pub struct CollectedFeesResponse {
+    pub fees: Vec<Coin>,
+}
+
+/// Response to [`QueryMsg::Hooks`].
+#[cw_serde]
+pub struct HooksResponse {
+    pub hooks: Vec<HookResponse>,
+}
+
+/// Contract notified when a POAP is minted, burned or transferred.
+#[cw_serde]
+pub struct HookResponse {
+    pub contract: String,
+    /// If true a failure of the contract is ignored,
+    /// otherwise it reverts the action that has notified the contract.
+    pub ignore_errors: bool,
+}
+
+/// Response to [`QueryMsg::Config`].
+#[cw_serde]
+pub struct ConfigResponse {
+    /// Name of the POAP contract.
+    pub name: String,
+    /// Symbol of the POAP contract.
+    pub symbol: String,
+    /// Who controls the contract, None if the ownership has been renounced.
+    pub admin: Option<String>,
+    pub minter: Option<String>,
+    pub metadata_uri: String,
+    pub is_transferable: bool,
+    pub transfer_policy: Option<TransferPolicy>,
+    pub is_mintable: bool,
+    pub mint_start_time: Option<Scheduled>,
+    pub mint_end_time: Option<Expiration>,
+    /// Whether the users can mint the POAP at the current block.
+    pub mint_status: MintStatus,
+    /// Number of POAPs that currently exist.
+    pub num_tokens: u64,
+    pub max_supply: Option<u64>,
+    pub per_address_limit: u32,
+    pub mint_price: Vec<Coin>,
+    /// Address that is allowed to pause the contract.
+    pub pauser: Option<String>,
+    pub is_paused: bool,
+    pub announce: Option<AnnounceConfig>,
+}
+
+/// Whether the users can mint the POAPs of an event.
+#[cw_serde]
+pub enum MintStatus {
+    /// The mint period has not started yet.
+    NotStarted,
+    /// The users can mint the POAPs.
+    Open,
+    /// The mint period has ended.
+    Ended,
+    /// The minting has been disabled from the admin.
+    Disabled,
+    /// The contract is paused.
+    Paused,
+    /// The max supply has been reached.
+    SoldOut,
+}
+
+/// Response to [`QueryMsg::HolderAtHeight`].
+#[cw_serde]
+pub struct HolderAtHeightResponse {
+    /// Number of POAPs owned by the user.
+    pub balance: u64,
+    pub height: u64,
+}
+
+/// Response to [`QueryMsg::TotalSupplyAtHeight`].
+#[cw_serde]
+pub struct TotalSupplyAtHeightResponse {
+    pub total_supply: u64,
+    pub height: u64,
+}
+
+/// Response to [`QueryMsg::TransferPolicy`].
+#[cw_serde]
+pub struct TransferPolicyResponse {
+    pub policy: Option<TransferPolicy>,
+}
+
+/// Response to [`QueryMsg::IsPaused`].
+#[cw_serde]
+pub struct IsPausedResponse {
+    pub is_paused: bool,
+}
+
+/// Response to [`QueryMsg::Announce`].
+#[cw_serde]
+pub struct AnnounceResponse {
+    pub announce: Option<AnnounceConfig>,
+}
+
+/// Response to [`QueryMsg::TopReferrers`].
+#[cw_serde]
+pub struct TopReferrersResponse {
+    pub referrers: Vec<ReferralsResponse>,
+}
+
+/// Response to [`QueryMsg::Referrals`].
+#[cw_serde]
+pub struct ReferralsResponse {
+    pub referrer: String,
+    /// Number of users that minted their POAP with the referrer.
+    pub count: u64,
+}
@@ -1,35 +1,110 @@
-use crate::msg::{IsMintableResponse, IsTransferableResponse, MintStartEndTimeResponse, QueryMsg};
+use crate::error::ContractError;
+use crate::extension::PoapExtension;
+use crate::msg::{
+    AirdropFailure, AirdropFailuresResponse, AirdropJobResponse, AllowlistResponse,
+    AnnounceResponse, ClaimCodesCountResponse, CollectedFeesResponse, ConfigResponse,
+    EligibilityRulesResponse, EventInfo, EventResponse, EventsResponse, HolderAtHeightResponse,
+    HolderResponse, HoldersResponse, HookResponse, HooksResponse, IsMintableResponse,
+    IsPausedResponse, IsTransferableResponse, MetadataUriResponse, MintPriceResponse,
+    MintStartEndTimeResponse, MintStatus, MintersResponse, PoapOfResponse, QueryMsg,
+    ReferralsResponse, RegisteredMinter, RemainingSupplyResponse, RevocationResponse,
+    TopReferrersResponse, TotalSupplyAtHeightResponse, TransferPolicyResponse,
+    VoucherSignerResponse,
+};
 use crate::state::PoapContract;
-use cosmwasm_std::{to_binary, Binary, CustomMsg, Deps, Env, StdResult};
+use cosmwasm_std::{to_binary, Binary, CustomMsg, Deps, Env, Order, StdError, StdResult};
+use cw721::TokensResponse;
 use cw721_base::MinterResponse;
+use cw_storage_plus::Bound;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::fmt::Debug;
 
-impl<'a, T, C, E, Q> PoapContract<'a, T, C, E, Q>
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 100;
+
+impl<'a, T, C, E, Q, X> PoapContract<'a, T, C, E, Q, X>
 where
     T: Serialize + DeserializeOwned + Clone + Debug,
     C: CustomMsg,
     E: CustomMsg,
     Q: CustomMsg,
+    X: PoapExtension<T, C, E, Q>,
 {
     pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg<Q>) -> StdResult<Binary> {
         match msg {
             QueryMsg::Minter {} => to_binary(&self.minter(deps, env)?),
             QueryMsg::IsMintable {} => to_binary(&self.is_mintable(deps, env)?),
             QueryMsg::IsTransferable {} => to_binary(&self.is_transferable(deps, env)?),
+            QueryMsg::TransferPolicy {} => to_binary(&self.query_transfer_policy(deps, env)?),
+            QueryMsg::IsPaused {} => to_binary(&self.is_paused(deps, env)?),
+            QueryMsg::Announce {} => to_binary(&self.query_announce(deps, env)?),
             QueryMsg::MintStartEndTime {} => to_binary(&self.mint_start_end_time(deps, env)?),
+            QueryMsg::ClaimCodesCount {} => to_binary(&self.query_claim_codes_count(deps, env)?),
+            QueryMsg::Allowlist {} => to_binary(&self.allowlist(deps, env)?),
+            QueryMsg::VoucherSigner {} => to_binary(&self.voucher_signer(deps, env)?),
+            QueryMsg::EligibilityRules {} => to_binary(&self.query_eligibility_rules(deps, env)?),
+            QueryMsg::RemainingSupply {} => to_binary(&self.remaining_supply(deps, env)?),
+            QueryMsg::Event { event_id } => to_binary(&self.event(deps, env, event_id)?),
+            QueryMsg::Events { start_after, limit } => {
+                to_binary(&self.events(deps, env, start_after, limit)?)
+            }
+            QueryMsg::MetadataUri {} => to_binary(&self.query_metadata_uri(deps, env)?),
+            QueryMsg::AirdropJob { job_id } => to_binary(&self.airdrop_job(deps, env, job_id)?),
+            QueryMsg::AirdropFailures {
+                job_id,
+                start_after,
+                limit,
+            } => to_binary(&self.airdrop_failures(deps, env, job_id, start_after, limit)?),
+            QueryMsg::EventTokens {
+                event_id,
+                start_after,
+                limit,
+            } => to_binary(&self.event_tokens(deps, env, event_id, start_after, limit)?),
+            QueryMsg::Holders { start_after, limit } => {
+                to_binary(&self.holders(deps, env, start_after, limit)?)
+            }
+            QueryMsg::PoapOf {
+                user,
+                start_after,
+                limit,
+            } => to_binary(&self.poap_of(deps, env, user, start_after, limit)?),
+            QueryMsg::Revocation { token_id } => to_binary(&self.revocation(deps, env, token_id)?),
+            QueryMsg::Minters { start_after, limit } => {
+                to_binary(&self.registered_minters(deps, env, start_after, limit)?)
+            }
+            QueryMsg::MintPrice {} => to_binary(&self.query_mint_price(deps, env)?),
+            QueryMsg::CollectedFees {} => to_binary(&self.query_collected_fees(deps, env)?),
+            QueryMsg::Hooks {} => to_binary(&self.query_hooks(deps, env)?),
+            QueryMsg::Config {} => to_binary(&self.config(deps, env)?),
+            QueryMsg::HolderAtHeight { user, height } => {
+                to_binary(&self.holder_at_height(deps, env, user, height)?)
+            }
+            QueryMsg::TotalSupplyAtHeight { height } => {
+                to_binary(&self.total_supply_at_height(deps, env, height)?)
+            }
+            QueryMsg::TopReferrers { start_after, limit } => {
+                to_binary(&self.top_referrers(deps, env, start_after, limit)?)
+            }
+            QueryMsg::Referrals { referrer } => {
+                to_binary(&self.query_referrals(deps, env, referrer)?)
+            }
+            QueryMsg::Extension { msg } => self
+                .extension
+                .query(deps, env, msg)
+                .map_err(|e| StdError::generic_err(e.to_string())),
             _ => self.cw721_base.query(deps, env, msg.into()),
         }
     }
 }
 
-impl<'a, T, C, E, Q> PoapContract<'a, T, C, E, Q>
+impl<'a, T, C, E, Q, X> PoapContract<'a, T, C, E, Q, X>
 where
     T: Serialize + DeserializeOwned + Clone + Debug,
     C: CustomMsg,
     E: CustomMsg,
     Q: CustomMsg,
+    X: PoapExtension<T, C, E, Q>,
 {
     /// Gets the address of the contract minter.
     pub fn minter(&self, deps: Deps, _env: Env) -> StdResult<MinterResponse> {
@@ -55,6 +130,31 @@ where
         })
     }
 
+    /// Gets the policy that restricts the transfers of the POAPs.
+    pub fn query_transfer_policy(
+        &self,
+        deps: Deps,
+        _env: Env,
+    ) -> StdResult<TransferPolicyResponse> {
+        Ok(TransferPolicyResponse {
+            policy: self.transfer_policy.may_load(deps.storage)?,
+        })
+    }
+
+    /// Gets if the operations that change the contract state are paused.
+    pub fn is_paused(&self, deps: Deps, _env: Env) -> StdResult<IsPausedResponse> {
+        Ok(IsPausedResponse {
+            is_paused: self.is_paused.may_load(deps.storage)?.unwrap_or_default(),
+        })
+    }
+
+    /// Gets the configuration of the Desmos posts that announce the minted POAPs.
+    pub fn query_announce(&self, deps: Deps, _env: Env) -> StdResult<AnnounceResponse> {
+        Ok(AnnounceResponse {
+            announce: self.announce.may_load(deps.storage)?,
+        })
+    }
+
     /// Gets the time period in which it is possible to mint the POAP.
     pub fn mint_start_end_time(
         &self,
@@ -66,4 +166,510 @@ where
             end_time: self.mint_end_time.load(deps.storage)?,
         })
     }
+
+    /// Gets the number of claim codes that have not been used yet.
+    pub fn query_claim_codes_count(
+        &self,
+        deps: Deps,
+        _env: Env,
+    ) -> StdResult<ClaimCodesCountResponse> {
+        Ok(ClaimCodesCountResponse {
+            count: self.claim_codes_count(deps.storage)?,
+        })
+    }
+
+    /// Gets the Merkle root of the allowlist.
+    pub fn allowlist(&self, deps: Deps, _env: Env) -> StdResult<AllowlistResponse> {
+        Ok(AllowlistResponse {
+            merkle_root: self.allowlist_root.may_load(deps.storage)?,
+        })
+    }
+
+    /// Gets the public key that signs the mint vouchers.
+    pub fn voucher_signer(&self, deps: Deps, _env: Env) -> StdResult<VoucherSignerResponse> {
+        Ok(VoucherSignerResponse {
+            public_key: self.voucher_signer.may_load(deps.storage)?,
+        })
+    }
+
+    /// Gets the rules that the users must satisfy to mint a POAP.
+    pub fn query_eligibility_rules(
+        &self,
+        deps: Deps,
+        _env: Env,
+    ) -> StdResult<EligibilityRulesResponse> {
+        Ok(EligibilityRulesResponse {
+            rules: self.eligibility_rules(deps.storage)?,
+        })
+    }
+
+    /// Gets the number of POAPs that can still be minted.
+    pub fn remaining_supply(&self, deps: Deps, _env: Env) -> StdResult<RemainingSupplyResponse> {
+        let max_supply = self.max_supply.may_load(deps.storage)?;
+        let supply = self.issued_poaps(deps.storage)?;
+
+        Ok(RemainingSupplyResponse {
+            max_supply,
+            supply,
+            remaining: max_supply.map(|max_supply| max_supply.saturating_sub(supply)),
+        })
+    }
+
+    /// Gets the metadata URI of the contract default event.
+    pub fn query_metadata_uri(&self, deps: Deps, _env: Env) -> StdResult<MetadataUriResponse> {
+        Ok(MetadataUriResponse {
+            metadata_uri: self.metadata_uri.load(deps.storage)?,
+            is_frozen: self
+                .metadata_frozen
+                .may_load(deps.storage)?
+                .unwrap_or(false),
+        })
+    }
+
+    /// Gets the configuration of an event.
+    /// * `event_id` - Id of the event.
+    pub fn event(&self, deps: Deps, _env: Env, event_id: u64) -> StdResult<EventResponse> {
+        Ok(EventResponse {
+            event_id,
+            info: self.events.load(deps.storage, event_id)?,
+        })
+    }
+
+    /// Lists the events hosted by the contract.
+    /// * `start_after` - Id of the event after which the listing starts.
+    /// * `limit` - Maximum number of events to return.
+    pub fn events(
+        &self,
+        deps: Deps,
+        _env: Env,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<EventsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let events = self
+            .events
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(event_id, info)| EventResponse { event_id, info }))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(EventsResponse { events })
+    }
+
+    /// Lists the ids of the POAPs minted for an event.
+    /// * `event_id` - Id of the event.
+    /// * `start_after` - Id of the POAP after which the listing starts.
+    /// * `limit` - Maximum number of ids to return.
+    pub fn event_tokens(
+        &self,
+        deps: Deps,
+        _env: Env,
+        event_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let tokens = self
+            .event_tokens
+            .prefix(event_id)
+            .keys(
+                deps.storage,
+                start_after.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(TokensResponse { tokens })
+    }
+
+    /// Gets the progress of an airdrop job.
+    /// * `job_id` - Id of the airdrop job.
+    pub fn airdrop_job(&self, deps: Deps, _env: Env, job_id: u64) -> StdResult<AirdropJobResponse> {
+        let job = self.airdrop_jobs.load(deps.storage, job_id)?;
+
+        Ok(AirdropJobResponse {
+            job_id,
+            event_id: job.event_id,
+            total_recipients: job.total_recipients,
+            processed: job.processed,
+            minted: job.minted,
+            failed: job.failed,
+            is_cancelled: job.is_cancelled,
+            error: job.error,
+        })
+    }
+
+    /// Lists the recipients of an airdrop job that have been skipped.
+    /// * `job_id` - Id of the airdrop job.
+    /// * `start_after` - Position in the recipients list after which the listing starts.
+    /// * `limit` - Maximum number of failures to return.
+    pub fn airdrop_failures(
+        &self,
+        deps: Deps,
+        _env: Env,
+        job_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<AirdropFailuresResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let failures = self
+            .airdrop_failures
+            .prefix(job_id)
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| {
+                item.map(|(index, result)| AirdropFailure {
+                    index,
+                    user: result.user,
+                    status: result.status,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(AirdropFailuresResponse { failures })
+    }
+
+    /// Lists the holders of the POAPs together with the information about their mint.
+    /// * `start_after` - Id of the POAP after which the listing starts.
+    /// * `limit` - Maximum number of holders to return.
+    pub fn holders(
+        &self,
+        deps: Deps,
+        _env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<HoldersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let holders = self
+            .cw721_base
+            .tokens
+            .range(
+                deps.storage,
+                start_after.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| {
+                let (token_id, token) = item?;
+                Ok(HolderResponse {
+                    owner: token.owner.to_string(),
+                    mint_info: self.mint_info.may_load(deps.storage, &token_id)?,
+                    token_id,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(HoldersResponse { holders })
+    }
+
+    /// Lists the POAPs owned by a user together with the information about their mint.
+    /// * `user` - Address of the user.
+    /// * `start_after` - Id of the POAP after which the listing starts.
+    /// * `limit` - Maximum number of POAPs to return.
+    pub fn poap_of(
+        &self,
+        deps: Deps,
+        _env: Env,
+        user: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<PoapOfResponse> {
+        let owner = deps.api.addr_validate(&user)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let poaps = self
+            .cw721_base
+            .tokens
+            .idx
+            .owner
+            .prefix(owner)
+            .keys(
+                deps.storage,
+                start_after.map(|token_id| Bound::ExclusiveRaw(token_id.into_bytes())),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|token_id| {
+                let token_id = token_id?;
+                Ok(HolderResponse {
+                    owner: user.clone(),
+                    mint_info: self.mint_info.may_load(deps.storage, &token_id)?,
+                    token_id,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(PoapOfResponse { poaps })
+    }
+
+    /// Gets why a POAP has been revoked.
+    /// * `token_id` - Id of the revoked POAP.
+    pub fn revocation(
+        &self,
+        deps: Deps,
+        _env: Env,
+        token_id: String,
+    ) -> StdResult<RevocationResponse> {
+        Ok(RevocationResponse {
+            info: self.revocations.load(deps.storage, &token_id)?,
+            token_id,
+        })
+    }
+
+    /// Lists the minters registered by the admin.
+    /// * `start_after` - Address of the minter after which the listing starts.
+    /// * `limit` - Maximum number of minters to return.
+    pub fn registered_minters(
+        &self,
+        deps: Deps,
+        _env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<MintersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|minter| deps.api.addr_validate(&minter))
+            .transpose()?;
+        let minters = self
+            .minters
+            .range(
+                deps.storage,
+                start_after.as_ref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| {
+                item.map(|(minter, info)| RegisteredMinter {
+                    minter: minter.to_string(),
+                    quota: info.quota,
+                    minted: info.minted,
+                    remaining_quota: info.quota.map(|quota| quota.saturating_sub(info.minted)),
+                    expiration: info.expiration,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(MintersResponse { minters })
+    }
+
+    /// Gets the price that the users pay to mint a POAP.
+    pub fn query_mint_price(&self, deps: Deps, _env: Env) -> StdResult<MintPriceResponse> {
+        Ok(MintPriceResponse {
+            mint_price: self.mint_price.may_load(deps.storage)?.unwrap_or_default(),
+        })
+    }
+
+    /// Gets the fees paid to mint the POAPs that have not been withdrawn yet.
+    pub fn query_collected_fees(&self, deps: Deps, _env: Env) -> StdResult<CollectedFeesResponse> {
+        Ok(CollectedFeesResponse {
+            fees: self.collected_fees(deps.storage)?,
+        })
+    }
+
+    /// Gets the contracts that are notified when a POAP is minted, burned or transferred.
+    pub fn query_hooks(&self, deps: Deps, _env: Env) -> StdResult<HooksResponse> {
+        let hooks = self
+            .hooks
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                item.map(|(contract, ignore_errors)| HookResponse {
+                    contract: contract.to_string(),
+                    ignore_errors,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(HooksResponse { hooks })
+    }
+
+    /// Gets the whole configuration of the contract and of its default event,
+    /// together with the current mint status.
+    pub fn config(&self, deps: Deps, env: Env) -> StdResult<ConfigResponse> {
+        let contract_info = self.cw721_base.contract_info.load(deps.storage)?;
+        let event = self
+            .event_info(deps.storage, None)
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+        let max_supply = self.max_supply.may_load(deps.storage)?;
+        let is_paused = self.is_paused.may_load(deps.storage)?.unwrap_or_default();
+        let mint_status = self.mint_status(
+            &event,
+            &env,
+            is_paused,
+            self.issued_poaps(deps.storage)?,
+            max_supply,
+        );
+
+        Ok(ConfigResponse {
+            name: contract_info.name,
+            symbol: contract_info.symbol,
+            admin: cw_ownable::get_ownership(deps.storage)?
+                .owner
+                .map(|admin| admin.to_string()),
+            minter: event.minter.as_ref().map(|minter| minter.to_string()),
+            mint_status,
+            metadata_uri: event.metadata_uri,
+            is_transferable: event.is_transferable,
+            transfer_policy: self.transfer_policy.may_load(deps.storage)?,
+            is_mintable: event.is_mintable,
+            mint_start_time: event.mint_start_time,
+            mint_end_time: event.mint_end_time,
+            num_tokens: self.cw721_base.token_count(deps.storage)?,
+            max_supply,
+            per_address_limit: self.per_address_limit(deps.storage)?,
+            mint_price: self.mint_price.may_load(deps.storage)?.unwrap_or_default(),
+            pauser: self
+                .pauser
+                .may_load(deps.storage)?
+                .map(|pauser| pauser.to_string()),
+            is_paused,
+            announce: self.announce.may_load(deps.storage)?,
+        })
+    }
+
+    /// Gets the number of POAPs owned by a user at the beginning of a block.
+    /// * `user` - Address of the user.
+    /// * `height` - Height of the block, if None the current number of POAPs is returned.
+    pub fn holder_at_height(
+        &self,
+        deps: Deps,
+        env: Env,
+        user: String,
+        height: Option<u64>,
+    ) -> StdResult<HolderAtHeightResponse> {
+        let user = deps.api.addr_validate(&user)?;
+        let balance = match height {
+            Some(height) => self
+                .holder_balances
+                .may_load_at_height(deps.storage, &user, height)?,
+            None => self.holder_balances.may_load(deps.storage, &user)?,
+        };
+
+        Ok(HolderAtHeightResponse {
+            balance: balance.unwrap_or_default(),
+            height: height.unwrap_or(env.block.height),
+        })
+    }
+
+    /// Gets the number of existing POAPs at the beginning of a block.
+    /// * `height` - Height of the block, if None the current number of POAPs is returned.
+    pub fn total_supply_at_height(
+        &self,
+        deps: Deps,
+        env: Env,
+        height: Option<u64>,
+    ) -> StdResult<TotalSupplyAtHeightResponse> {
+        let total_supply = match height {
+            Some(height) => self.total_supply.may_load_at_height(deps.storage, height)?,
+            None => self.total_supply.may_load(deps.storage)?,
+        };
+
+        Ok(TotalSupplyAtHeightResponse {
+            total_supply: total_supply.unwrap_or_default(),
+            height: height.unwrap_or(env.block.height),
+        })
+    }
+
+    /// Lists the referrers ordered by their number of referrals, starting from the highest.
+    /// * `start_after` - Address of the referrer after which the listing starts.
+    /// * `limit` - Maximum number of referrers to return.
+    pub fn top_referrers(
+        &self,
+        deps: Deps,
+        _env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TopReferrersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|referrer| {
+                let referrer = deps.api.addr_validate(&referrer)?;
+                let count = self.referrals.load(deps.storage, &referrer)?;
+                Ok::<_, StdError>((count, referrer))
+            })
+            .transpose()?;
+        let referrers = self
+            .referrers_by_count
+            .keys(
+                deps.storage,
+                None,
+                start_after
+                    .as_ref()
+                    .map(|(count, referrer)| Bound::exclusive((*count, referrer))),
+                Order::Descending,
+            )
+            .take(limit)
+            .map(|item| {
+                let (count, referrer) = item?;
+                Ok(ReferralsResponse {
+                    referrer: referrer.to_string(),
+                    count,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(TopReferrersResponse { referrers })
+    }
+
+    /// Gets the number of users that minted their POAP with a referrer.
+    /// * `referrer` - Address of the referrer.
+    pub fn query_referrals(
+        &self,
+        deps: Deps,
+        _env: Env,
+        referrer: String,
+    ) -> StdResult<ReferralsResponse> {
+        let referrer_addr = deps.api.addr_validate(&referrer)?;
+        let count = self.referrals.may_load(deps.storage, &referrer_addr)?;
+
+        Ok(ReferralsResponse {
+            referrer,
+            count: count.unwrap_or_default(),
+        })
+    }
+
+    /// Gets whether the users can mint the POAPs of an event at the current block.
+    /// * `event` - Configuration of the event.
+    /// * `is_paused` - Whether the contract is paused.
+    /// * `issued_poaps` - Number of POAPs minted so far, burned ones included.
+    /// * `max_supply` - Maximum number of POAPs that can be minted, if None there is no limit.
+    pub fn mint_status(
+        &self,
+        event: &EventInfo,
+        env: &Env,
+        is_paused: bool,
+        issued_poaps: u64,
+        max_supply: Option<u64>,
+    ) -> MintStatus {
+        if is_paused {
+            return MintStatus::Paused;
+        }
+
+        if matches!(max_supply, Some(max_supply) if issued_poaps >= max_supply) {
+            return MintStatus::SoldOut;
+        }
+
+        if !event.is_mintable {
+            return MintStatus::Disabled;
+        }
+
+        match self.assert_mint_time(event, env) {
+            Ok(()) => MintStatus::Open,
+            Err(ContractError::MintTimeNotStarted {}) => MintStatus::NotStarted,
+            Err(_) => MintStatus::Ended,
+        }
+    }
 }
@@ -1,18 +1,27 @@
-use cosmwasm_std::{Addr, CustomMsg, Timestamp};
+use crate::extension::NoopExtension;
+use crate::msg::{
+    AnnounceConfig, EligibilityRule, EventInfo, MintInfo, MintToResult, RevocationInfo,
+    TransferPolicy,
+};
+use cosmwasm_std::{Addr, Binary, Coin, CustomMsg, Empty, Uint128};
 use cw721_base::Cw721Contract;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
+use cw_utils::{Expiration, Scheduled};
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-pub struct PoapContract<'a, T, C, E, Q>
+pub struct PoapContract<'a, T, C, E, Q, X = NoopExtension>
 where
     T: Serialize + DeserializeOwned + Clone,
     Q: CustomMsg,
     E: CustomMsg,
 {
     pub cw721_base: Cw721Contract<'a, T, C, E, Q>,
+    /// Custom behaviour plugged in by the contracts that embed this one.
+    pub extension: X,
     /// The URI where users can view the associated metadata for the POAPs,
     /// ideally following the ERC-721 metadata scheme in a JSON file.
+    /// The `{token_id}` placeholder is replaced with the id of each minted POAP.
     pub metadata_uri: Item<'a, String>,
     /// Additional address that is allowed to mint tokens on behalf of other users.
     pub minter: Item<'a, Option<Addr>>,
@@ -20,20 +29,148 @@ where
     pub is_transferable: Item<'a, bool>,
     /// Indicates whether users can mint the POAPs.
     pub is_mintable: Item<'a, bool>,
-    /// Identifies the timestamp at which the minting of the POAP will be enabled.
+    /// Identifies the block height or timestamp at which the minting of the POAP will be enabled.
     /// If not set, the minting is always enabled.
-    pub mint_start_time: Item<'a, Option<Timestamp>>,
-    /// Identifies the timestamp at which the minting of the POAP will be disabled.
+    pub mint_start_time: Item<'a, Option<Scheduled>>,
+    /// Identifies the block height or timestamp at which the minting of the POAP will be disabled.
     /// If not set, the minting will never end.
-    pub mint_end_time: Item<'a, Option<Timestamp>>,
+    pub mint_end_time: Item<'a, Option<Expiration>>,
+    /// Serial number that will be used as id of the next minted POAP.
+    /// This value is never decremented, so the ids of the burned POAPs are not reused.
+    pub next_poap_id: Item<'a, u64>,
+    /// SHA-256 hashes of the claim codes that can be used to mint a POAP.
+    pub claim_codes: Map<'a, &'a [u8], Empty>,
+    /// Number of claim codes that have not been used yet.
+    pub claim_codes_count: Item<'a, u64>,
+    /// Root of the Merkle tree built from the addresses that are allowed to mint
+    /// a POAP with a proof. If not set, the allowlist minting is disabled.
+    pub allowlist_root: Item<'a, Binary>,
+    /// secp256k1 public key of the backend that signs the mint vouchers.
+    /// If not set, the minting with a voucher is disabled.
+    pub voucher_signer: Item<'a, Binary>,
+    /// Nonces of the vouchers that have already been used to mint a POAP.
+    pub spent_voucher_nonces: Map<'a, u64, Empty>,
+    /// Rules that the users must satisfy to mint a POAP.
+    pub eligibility_rules: Item<'a, Vec<EligibilityRule>>,
+    /// Maximum number of POAPs that can ever be minted, burned ones included.
+    /// If not set, there is no limit.
+    pub max_supply: Item<'a, u64>,
+    /// Maximum number of POAPs that an address can own.
+    pub per_address_limit: Item<'a, u32>,
+    /// Events hosted by the contract in addition to the default one.
+    pub events: Map<'a, u64, EventInfo>,
+    /// Serial number that will be used as id of the next created event.
+    pub next_event_id: Item<'a, u64>,
+    /// Id of the event for which each POAP has been minted.
+    /// The POAPs minted for the default event are not stored here.
+    pub token_events: Map<'a, &'a str, u64>,
+    /// Ids of the POAPs minted for each event.
+    pub event_tokens: Map<'a, (u64, &'a str), Empty>,
+    /// Indicates whether the metadata URIs have been permanently frozen.
+    pub metadata_frozen: Item<'a, bool>,
+    /// Airdrop jobs that mint the POAPs to a stored list of recipients.
+    pub airdrop_jobs: Map<'a, u64, AirdropJob<T>>,
+    /// Serial number that will be used as id of the next airdrop job.
+    pub next_airdrop_id: Item<'a, u64>,
+    /// Recipients of each airdrop job, indexed by their position in the list.
+    pub airdrop_recipients: Map<'a, (u64, u64), String>,
+    /// Recipients of each airdrop job that have been skipped while processing the job,
+    /// indexed by their position in the recipients list.
+    pub airdrop_failures: Map<'a, (u64, u64), MintToResult>,
+    /// Information about the mint of each POAP.
+    /// The POAPs minted before this information was tracked are not stored here.
+    pub mint_info: Map<'a, &'a str, MintInfo>,
+    /// Information about the POAPs that have been revoked.
+    pub revocations: Map<'a, &'a str, RevocationInfo>,
+    /// Additional minters registered by the admin, that can mint the POAPs on behalf of other users.
+    pub minters: Map<'a, &'a Addr, MinterInfo>,
+    /// Price that the users pay to mint a POAP with the `Mint` message.
+    /// If not set, the minting is free.
+    pub mint_price: Item<'a, Vec<Coin>>,
+    /// Fees paid to mint the POAPs that have not been withdrawn yet, indexed by denom.
+    pub collected_fees: Map<'a, &'a str, Uint128>,
+    /// Contracts notified when a POAP is minted, burned or transferred,
+    /// each one with a flag that indicates whether its failures are ignored.
+    pub hooks: Map<'a, &'a Addr, bool>,
+    /// Number of POAPs owned by each user, with a snapshot of every block in which it changed.
+    pub holder_balances: SnapshotMap<'a, &'a Addr, u64>,
+    /// Number of existing POAPs, with a snapshot of every block in which it changed.
+    pub total_supply: SnapshotItem<'a, u64>,
+    /// Policy that restricts the transfers of the POAPs.
+    /// If not set, the transferable POAPs can be freely transferred.
+    pub transfer_policy: Item<'a, TransferPolicy>,
+    /// Ids of the POAPs that have been transferred at least once by their owner.
+    pub transferred_tokens: Map<'a, &'a str, Empty>,
+    /// Address that is allowed to pause the contract, but not to unpause it.
+    pub pauser: Item<'a, Addr>,
+    /// Indicates whether the operations that change the contract state are paused.
+    pub is_paused: Item<'a, bool>,
+    /// Configuration of the Desmos posts that announce the minted POAPs.
+    /// If not set, the mints are not announced.
+    pub announce: Item<'a, AnnounceConfig>,
+    /// Number of users that minted their POAP with each referrer.
+    pub referrals: Map<'a, &'a Addr, u64>,
+    /// Referrers indexed by their number of referrals, used to list the top referrers.
+    pub referrers_by_count: Map<'a, (u64, &'a Addr), Empty>,
+    /// Progress of the import of the POAPs minted with a v1 `cw721-poap` contract.
+    /// If not set, there are no POAPs left to import.
+    pub v1_import: Item<'a, V1Import>,
 }
 
-impl<'a, T, C, E, Q> PoapContract<'a, T, C, E, Q>
+/// Progress of the import of the POAPs minted with a v1 `cw721-poap` contract,
+/// which is performed across multiple transactions after the migration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct V1Import {
+    /// Address of the v1 cw721-poap contract.
+    pub cw721_address: Addr,
+    /// Id of the last imported POAP, None if no POAP has been imported yet.
+    pub start_after: Option<String>,
+}
+
+/// Airdrop job that mints the POAPs to a stored list of recipients across
+/// multiple transactions.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AirdropJob<T> {
+    /// Id of the event for which the POAPs are minted, None for the contract default event.
+    pub event_id: Option<u64>,
+    /// Address that has created the job, its minter quota is used for all the recipients.
+    pub creator: Addr,
+    /// Extension of the minted POAPs.
+    pub extension: T,
+    /// Number of recipients uploaded so far.
+    pub total_recipients: u64,
+    /// Number of recipients that have been processed.
+    pub processed: u64,
+    /// Number of POAPs that have been minted.
+    pub minted: u64,
+    /// Number of recipients that have been skipped.
+    pub failed: u64,
+    /// Indicates whether the job has been cancelled.
+    pub is_cancelled: bool,
+    /// Error that has stopped the job, None if the job can still be processed.
+    pub error: Option<String>,
+}
+
+/// Configuration of a minter registered by the admin.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MinterInfo {
+    /// Maximum number of POAPs that the minter can mint, if None there is no limit.
+    pub quota: Option<u64>,
+    /// Number of POAPs minted so far, including the ones reserved for
+    /// the recipients of its pending airdrop jobs.
+    pub minted: u64,
+    /// When the minter loses the minting permissions, if None they never expire.
+    pub expiration: Option<Expiration>,
+}
+
+impl<'a, T, C, E, Q, X> PoapContract<'a, T, C, E, Q, X>
 where
     T: Serialize + DeserializeOwned + Clone,
     E: CustomMsg,
     Q: CustomMsg,
+    X: Default,
 {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         metadata_uri_key: &'a str,
         minter_key: &'a str,
@@ -41,24 +178,108 @@ where
         is_mintable_key: &'a str,
         mint_start_time_key: &'a str,
         mint_end_time_key: &'a str,
+        next_poap_id_key: &'a str,
+        claim_codes_key: &'a str,
+        claim_codes_count_key: &'a str,
+        allowlist_root_key: &'a str,
+        voucher_signer_key: &'a str,
+        spent_voucher_nonces_key: &'a str,
+        eligibility_rules_key: &'a str,
+        max_supply_key: &'a str,
+        per_address_limit_key: &'a str,
+        events_key: &'a str,
+        next_event_id_key: &'a str,
+        token_events_key: &'a str,
+        event_tokens_key: &'a str,
+        metadata_frozen_key: &'a str,
+        airdrop_jobs_key: &'a str,
+        next_airdrop_id_key: &'a str,
+        airdrop_recipients_key: &'a str,
+        airdrop_failures_key: &'a str,
+        mint_info_key: &'a str,
+        revocations_key: &'a str,
+        minters_key: &'a str,
+        mint_price_key: &'a str,
+        collected_fees_key: &'a str,
+        hooks_key: &'a str,
+        holder_balances_key: &'a str,
+        holder_balances_checkpoints_key: &'a str,
+        holder_balances_changelog_key: &'a str,
+        total_supply_key: &'a str,
+        total_supply_checkpoints_key: &'a str,
+        total_supply_changelog_key: &'a str,
+        transfer_policy_key: &'a str,
+        transferred_tokens_key: &'a str,
+        pauser_key: &'a str,
+        is_paused_key: &'a str,
+        announce_key: &'a str,
+        referrals_key: &'a str,
+        referrers_by_count_key: &'a str,
+        v1_import_key: &'a str,
     ) -> Self {
         Self {
             cw721_base: Cw721Contract::default(),
+            extension: X::default(),
             metadata_uri: Item::new(metadata_uri_key),
             minter: Item::new(minter_key),
             is_transferable: Item::new(is_transferable_key),
             is_mintable: Item::new(is_mintable_key),
             mint_start_time: Item::new(mint_start_time_key),
             mint_end_time: Item::new(mint_end_time_key),
+            next_poap_id: Item::new(next_poap_id_key),
+            claim_codes: Map::new(claim_codes_key),
+            claim_codes_count: Item::new(claim_codes_count_key),
+            allowlist_root: Item::new(allowlist_root_key),
+            voucher_signer: Item::new(voucher_signer_key),
+            spent_voucher_nonces: Map::new(spent_voucher_nonces_key),
+            eligibility_rules: Item::new(eligibility_rules_key),
+            max_supply: Item::new(max_supply_key),
+            per_address_limit: Item::new(per_address_limit_key),
+            events: Map::new(events_key),
+            next_event_id: Item::new(next_event_id_key),
+            token_events: Map::new(token_events_key),
+            event_tokens: Map::new(event_tokens_key),
+            metadata_frozen: Item::new(metadata_frozen_key),
+            airdrop_jobs: Map::new(airdrop_jobs_key),
+            next_airdrop_id: Item::new(next_airdrop_id_key),
+            airdrop_recipients: Map::new(airdrop_recipients_key),
+            airdrop_failures: Map::new(airdrop_failures_key),
+            mint_info: Map::new(mint_info_key),
+            revocations: Map::new(revocations_key),
+            minters: Map::new(minters_key),
+            mint_price: Item::new(mint_price_key),
+            collected_fees: Map::new(collected_fees_key),
+            hooks: Map::new(hooks_key),
+            holder_balances: SnapshotMap::new(
+                holder_balances_key,
+                holder_balances_checkpoints_key,
+                holder_balances_changelog_key,
+                Strategy::EveryBlock,
+            ),
+            total_supply: SnapshotItem::new(
+                total_supply_key,
+                total_supply_checkpoints_key,
+                total_supply_changelog_key,
+                Strategy::EveryBlock,
+            ),
+            transfer_policy: Item::new(transfer_policy_key),
+            transferred_tokens: Map::new(transferred_tokens_key),
+            pauser: Item::new(pauser_key),
+            is_paused: Item::new(is_paused_key),
+            announce: Item::new(announce_key),
+            referrals: Map::new(referrals_key),
+            referrers_by_count: Map::new(referrers_by_count_key),
+            v1_import: Item::new(v1_import_key),
         }
     }
 }
 
-impl<T, C, E, Q> Default for PoapContract<'static, T, C, E, Q>
+impl<T, C, E, Q, X> Default for PoapContract<'static, T, C, E, Q, X>
 where
     T: Serialize + DeserializeOwned + Clone,
     E: CustomMsg,
     Q: CustomMsg,
+    X: Default,
 {
     fn default() -> Self {
         Self::new(
@@ -68,6 +289,44 @@ where
             "is_mintable",
             "mint_start_time",
             "mint_end_time",
+            "next_poap_id",
+            "claim_codes",
+            "claim_codes_count",
+            "allowlist_root",
+            "voucher_signer",
+            "spent_voucher_nonces",
+            "eligibility_rules",
+            "max_supply",
+            "per_address_limit",
+            "events",
+            "next_event_id",
+            "token_events",
+            "event_tokens",
+            "metadata_frozen",
+            "airdrop_jobs",
+            "next_airdrop_id",
+            "airdrop_recipients",
+            "airdrop_failures",
+            "mint_info",
+            "revocations",
+            "minters",
+            "mint_price",
+            "collected_fees",
+            "hooks",
+            "holder_balances",
+            "holder_balances__checkpoints",
+            "holder_balances__changelog",
+            "total_supply",
+            "total_supply__checkpoints",
+            "total_supply__changelog",
+            "transfer_policy",
+            "transferred_tokens",
+            "pauser",
+            "is_paused",
+            "announce",
+            "referrals",
+            "referrers_by_count",
+            "v1_import",
         )
     }
 }